@@ -0,0 +1,17 @@
+extern crate finviz_rs;
+
+use finviz_rs::{
+    market::Market,
+    common::Scrape
+};
+
+#[tokio::main]
+async fn main() -> Result<(),Box<dyn std::error::Error>>{
+    let r = Market::default()
+        .scrape().await?;
+    println!("{:?}", r.breadth);
+    for (signal, tickers) in r.signals.iter() {
+        println!("{}: {}", signal, tickers.join(", "));
+    }
+    Ok(())
+}
@@ -12,5 +12,8 @@ async fn main() -> Result<(),Box<dyn std::error::Error>>{
         .scrape().await?;
     println!("{}", r.news.to_table(Some(News::default_header()), Some(5)));
     println!("{}", r.blogs.to_table(Some(News::default_header()), Some(5)));
+    if let Some(item) = r.news.first() {
+        println!("{} {} ({})", item.published.to_rfc3339(), item.title, item.source);
+    }
     Ok(())
 }
@@ -16,5 +16,9 @@ async fn main() -> Result<(),Box<dyn std::error::Error>> {
     // output json to table
     let fundament_info = Tickers::new("AAPL").scrape().await?;
     println!("{}", from_dict_to_table(&fundament_info, 4).to_table(None, None));
+
+    // output the daily price history to table
+    let bars = Tickers::new("AAPL").history(TimeFrameType::Daily).await?;
+    println!("{}", bars.to_table(None, Some(5)));
     Ok(())
 }
@@ -0,0 +1,577 @@
+use crate::tickers::{ChartType, TimeFrameType, Tickers};
+use crate::common::TableData;
+use crate::future::FutureQuote;
+use crate::performance::PerformanceRow;
+use crate::web_scraper::{get_chart_image, save_image, ChartImage};
+use chrono::{Local, NaiveDate};
+use futures::stream::{self, StreamExt};
+use std::fmt;
+use std::time::Duration;
+use strum::EnumIter;
+use tokio::sync::Mutex;
+use tokio::time::Instant;
+
+/// Represents the size of a chart image.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChartSize {
+    /// the medium size used on the screener pages
+    Medium,
+    /// the large size used on the quote page
+    Large,
+}
+
+impl fmt::Display for ChartSize {
+
+    /// Formats the `ChartSize` that can be used as URL parameter in `ChartRequest`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ChartSize::Medium => write!(f, "m"),
+            ChartSize::Large => write!(f, "l"),
+        }
+    }
+}
+
+/// Represents a technical overlay drawn on a chart.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChartOverlay {
+    /// simple moving average of the given period
+    Sma(u32),
+    /// exponential moving average of the given period
+    Ema(u32),
+    /// Bollinger bands of the given period and number of standard deviations
+    Bollinger(u32, u32),
+    /// volume bars
+    Volume,
+}
+
+impl fmt::Display for ChartOverlay {
+
+    /// Formats the `ChartOverlay` that can be used in the `ta` URL parameter in `ChartRequest`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ChartOverlay::Sma(period) => write!(f, "sma_{}", period),
+            ChartOverlay::Ema(period) => write!(f, "ema_{}", period),
+            ChartOverlay::Bollinger(period, deviations) => write!(f, "bb_{}_{}", period, deviations),
+            ChartOverlay::Volume => write!(f, "vol"),
+        }
+    }
+}
+
+/// This struct represents the options of a chart image, which is used to generate the URL of
+/// `chart.ashx` for a ticker.
+///
+/// # Example
+///
+/// ```
+/// use finviz_rs::{
+///     chart::{ChartRequest, ChartOverlay, ChartSize},
+///     tickers::{Tickers, TimeFrameType, ChartType},
+/// };
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(),Box<dyn std::error::Error>> {
+///     let mut request = ChartRequest::new();
+///     request.set_timeframe(TimeFrameType::Minute5)
+///         .set_chart_type(ChartType::CANDLE)
+///         .set_size(ChartSize::Large)
+///         .add_overlay(ChartOverlay::Sma(20))
+///         .add_overlay(ChartOverlay::Volume);
+///     Tickers::new("AAPL").chart(&request, ".").await?;
+///     Ok(())
+/// }
+/// ```
+///
+/// The above example demonstrates how to download a 5 minutes chart with SMA20 and volume.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChartRequest {
+    timeframe: TimeFrameType,
+    chart_type: ChartType,
+    size: Option<ChartSize>,
+    overlays: Vec<ChartOverlay>,
+    file_name_template: String,
+}
+
+impl Default for ChartRequest {
+
+    /// Creates a new instance of `ChartRequest` using the default constructor
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChartRequest {
+    const BASE_URL: &'static str = "https://finviz.com/chart.ashx";
+
+    /// The default template of the saved file name, see `set_file_name_template`.
+    pub const DEFAULT_FILE_NAME_TEMPLATE: &'static str = "{ticker}_{timeframe}_{chart_type}";
+
+    /// Creates a new `ChartRequest` of a daily candle chart.
+    pub fn new() -> Self {
+        Self {
+            timeframe: TimeFrameType::Daily,
+            chart_type: ChartType::CANDLE,
+            size: None,
+            overlays: Vec::new(),
+            file_name_template: ChartRequest::DEFAULT_FILE_NAME_TEMPLATE.to_string(),
+        }
+    }
+
+    /// Sets the timeframe of the chart.
+    pub fn set_timeframe(&mut self, timeframe: TimeFrameType) -> &mut Self {
+        self.timeframe = timeframe;
+        self
+    }
+
+    /// Sets the chart type.
+    pub fn set_chart_type(&mut self, chart_type: ChartType) -> &mut Self {
+        self.chart_type = chart_type;
+        self
+    }
+
+    /// Sets the size of the chart image.
+    pub fn set_size(&mut self, size: ChartSize) -> &mut Self {
+        self.size = Some(size);
+        self
+    }
+
+    /// Adds a technical overlay to the chart.
+    pub fn add_overlay(&mut self, overlay: ChartOverlay) -> &mut Self {
+        self.overlays.push(overlay);
+        self
+    }
+
+    /// Sets the template of the saved file name without extension. The placeholders `{ticker}`,
+    /// `{timeframe}`, `{chart_type}` and `{date}` (today as `YYYY-MM-DD`) are replaced, and the
+    /// extension is added according to the downloaded image type.
+    pub fn set_file_name_template(&mut self, template: &str) -> &mut Self {
+        self.file_name_template = template.to_owned();
+        self
+    }
+
+    /// Returns the timeframe of the chart.
+    pub fn timeframe(&self) -> TimeFrameType {
+        self.timeframe
+    }
+
+    /// Returns the chart type.
+    pub fn chart_type(&self) -> ChartType {
+        self.chart_type
+    }
+
+    /// Generates the URL of the chart image of the given ticker.
+    pub fn to_url(&self, ticker: &str) -> String {
+        let url_type = match self.chart_type {
+            ChartType::LINE => "l",
+            ChartType::CANDLE | ChartType::ADVANCED => "c",
+        };
+
+        let url_ta = match (&self.chart_type, &self.timeframe) {
+            _ if !self.overlays.is_empty() => {
+                self.overlays.iter().map(ChartOverlay::to_string).collect::<Vec<_>>().join(",")
+            },
+            (ChartType::ADVANCED, TimeFrameType::Daily) => "1".to_string(),
+            _ => "0".to_string(),
+        };
+
+        format!("{}?t={}&ty={}&ta={}&p={}{}", ChartRequest::BASE_URL,
+                ticker, url_type, url_ta, self.timeframe.to_url_param(),
+                self.size.map_or(String::new(), |s| format!("&s={}", s))
+        )
+    }
+
+    /// Generates the file name (without extension) of the chart image of the given ticker.
+    pub fn to_file_name(&self, ticker: &str) -> String {
+        self.render_file_name(ticker, Local::now().date_naive())
+    }
+
+    // helper function to replace the placeholders of the file name template
+    fn render_file_name(&self, ticker: &str, date: NaiveDate) -> String {
+        self.file_name_template
+            .replace("{ticker}", ticker)
+            .replace("{timeframe}", &self.timeframe.to_string())
+            .replace("{chart_type}", &self.chart_type.to_string())
+            .replace("{date}", &date.format("%Y-%m-%d").to_string())
+            .replace(['/', '\\'], "_")
+    }
+}
+
+/// Represents the instruments whose charts are not served by `chart.ashx`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Instrument {
+    /// a ticker returned by `Future`, e.g. "ES"
+    Future,
+    /// a ticker returned by `Forex`, e.g. "EURUSD"
+    Forex,
+    /// a ticker returned by `Crypto`, e.g. "BTCUSD"
+    Crypto,
+}
+
+/// Represents the timeframes offered by the charts of futures, forex and crypto.
+#[derive(Clone, Copy, Debug, PartialEq, EnumIter)]
+pub enum InstrumentTimeFrame {
+    /// 5 minutes
+    Minute5,
+    /// hourly
+    Hourly,
+    /// daily
+    Daily,
+    /// weekly
+    Weekly,
+    /// monthly
+    Monthly,
+}
+
+impl fmt::Display for InstrumentTimeFrame {
+
+    /// Formats the `InstrumentTimeFrame` that can be used as URL parameter in `InstrumentChart`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InstrumentTimeFrame::Minute5 => write!(f, "m5"),
+            InstrumentTimeFrame::Hourly => write!(f, "h1"),
+            InstrumentTimeFrame::Daily => write!(f, "d1"),
+            InstrumentTimeFrame::Weekly => write!(f, "w1"),
+            InstrumentTimeFrame::Monthly => write!(f, "mo"),
+        }
+    }
+}
+
+/// A ticker, or a scraped row of a ticker, whose chart can be retrieved by `InstrumentChart`, so the
+/// rows returned by `Forex`, `Crypto` and `Future` can be passed as they are.
+pub trait ChartTicker {
+
+    /// Returns the ticker of the chart
+    fn chart_ticker(&self) -> &str;
+}
+
+impl ChartTicker for str {
+    fn chart_ticker(&self) -> &str {
+        self
+    }
+}
+
+impl ChartTicker for String {
+    fn chart_ticker(&self) -> &str {
+        self
+    }
+}
+
+/// A row of `TableData` returned by `scrape`, with the ticker in the first column
+impl ChartTicker for Vec<String> {
+    fn chart_ticker(&self) -> &str {
+        self.first().map_or("", String::as_str)
+    }
+}
+
+impl ChartTicker for PerformanceRow {
+    fn chart_ticker(&self) -> &str {
+        &self.ticker
+    }
+}
+
+impl ChartTicker for FutureQuote {
+    fn chart_ticker(&self) -> &str {
+        &self.ticker
+    }
+}
+
+/// This struct represents the chart of a futures, forex or crypto ticker, as shown on the
+/// performance pages of those instruments.
+///
+/// # Example
+///
+/// ```
+/// use finviz_rs::{
+///     chart::InstrumentTimeFrame,
+///     forex::Forex,
+///     common::Scrape,
+/// };
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(),Box<dyn std::error::Error>> {
+///     let rows = Forex::default().rows().await?;
+///     for row in rows.iter().take(3) {
+///         Forex::chart(row, InstrumentTimeFrame::Daily, "charts").await?;
+///     }
+///     Ok(())
+/// }
+/// ```
+///
+/// The above example demonstrates how to download the charts of the first forex pairs.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct InstrumentChart {
+    instrument: Instrument,
+    timeframe: InstrumentTimeFrame,
+}
+
+impl InstrumentChart {
+
+    /// Creates a new `InstrumentChart` of the instrument type with the specified timeframe.
+    pub fn new(instrument: Instrument, timeframe: InstrumentTimeFrame) -> Self {
+        Self { instrument, timeframe }
+    }
+
+    /// Generates the URL of the chart image of the given ticker.
+    pub fn to_url(&self, ticker: &str) -> String {
+        let endpoint = match self.instrument {
+            Instrument::Future => "fut_image.ashx",
+            Instrument::Forex => "fx_image.ashx",
+            Instrument::Crypto => "crypto_image.ashx",
+        };
+        format!("https://finviz.com/{}?{}_{}_l.png", endpoint, ticker.to_lowercase(), self.timeframe)
+    }
+
+    /// Retrieves the chart image of the ticker into memory.
+    pub async fn image(&self, ticker: &str) -> Result<ChartImage, Box<dyn std::error::Error>> {
+        get_chart_image(&self.to_url(ticker)).await
+    }
+
+    /// Retrieves the chart image of the ticker or the row, return the String of the saved image path
+    /// `{out_dir}/{ticker}_{timeframe}.{extension}` on success, or error on failure.
+    pub async fn download<T: ChartTicker + ?Sized>(&self, row: &T, out_dir: &str) -> Result<String, Box<dyn std::error::Error>> {
+        let ticker = row.chart_ticker().trim();
+        if ticker.is_empty() {
+            return Err("Cannot download the chart of an empty ticker".into());
+        }
+        let image = self.image(ticker).await?;
+        save_image(&image, out_dir, &format!("{}_{}", ticker.replace('/', "_"), self.timeframe))
+    }
+}
+
+/// The progress of `ChartDownloader::download`, reported after each chart
+#[derive(Debug, Clone)]
+pub struct ChartProgress {
+    /// number of finished charts, including failures
+    pub completed: usize,
+    /// number of all charts
+    pub total: usize,
+    /// the ticker of the finished chart
+    pub ticker: String,
+    /// the saved image path on success, or the error message on failure
+    pub result: Result<String, String>,
+}
+
+/// The result of `ChartDownloader::download`
+#[derive(Debug, Clone, Default)]
+pub struct ChartManifest {
+    /// pairs of ticker and saved image path
+    pub succeeded: Vec<(String, String)>,
+    /// pairs of ticker and error message
+    pub failed: Vec<(String, String)>,
+}
+
+/// This struct downloads the charts of many tickers concurrently with the same `ChartRequest`.
+///
+/// # Example
+///
+/// ```
+/// use finviz_rs::{
+///     chart::{ChartDownloader, ChartRequest, tickers_from_table},
+///     screener::Screener,
+///     screener_type::ScreenerType,
+///     signal_type::SignalType,
+///     common::Scrape,
+/// };
+/// use std::time::Duration;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(),Box<dyn std::error::Error>> {
+///     let table = Screener::new(ScreenerType::Overview)
+///         .set_signal(SignalType::TopGainers)
+///         .scrape().await?;
+///     let manifest = ChartDownloader::new(ChartRequest::new())
+///         .set_concurrency(4)
+///         .set_interval(Duration::from_millis(200))
+///         .download(&tickers_from_table(&table), "charts", |p| {
+///             println!("{}/{} {}", p.completed, p.total, p.ticker);
+///         }).await;
+///     println!("{} failed", manifest.failed.len());
+///     Ok(())
+/// }
+/// ```
+///
+/// The above example demonstrates how to download the charts of all tickers in a screener result.
+pub struct ChartDownloader {
+    request: ChartRequest,
+    concurrency: usize,
+    interval: Duration,
+}
+
+impl ChartDownloader {
+
+    /// Creates a new `ChartDownloader` with the chart options used for every ticker, which downloads
+    /// 4 charts at a time without rate limiting.
+    pub fn new(request: ChartRequest) -> Self {
+        Self { request, concurrency: 4, interval: Duration::ZERO }
+    }
+
+    /// Sets the maximum number of charts downloaded at the same time.
+    pub fn set_concurrency(&mut self, concurrency: usize) -> &mut Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Sets the minimum interval between the start of two downloads.
+    pub fn set_interval(&mut self, interval: Duration) -> &mut Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Downloads the charts of the tickers to the output directory, calling `on_progress` after each
+    /// chart, and returns the manifest of successes and failures. A failed chart does not stop the others.
+    ///
+    /// To report the progress through a channel, send the `ChartProgress` in `on_progress`.
+    pub async fn download<F>(&self, tickers: &[String], out_dir: &str, mut on_progress: F) -> ChartManifest
+    where
+        F: FnMut(&ChartProgress),
+    {
+        let next_start = Mutex::new(Instant::now());
+        let total = tickers.len();
+
+        let mut results = stream::iter(tickers)
+            .map(|ticker| {
+                let next_start = &next_start;
+                async move {
+                    self.wait_for_turn(next_start).await;
+                    let result = Tickers::new(ticker)
+                        .chart(&self.request, out_dir).await
+                        .map_err(|err| err.to_string());
+                    (ticker.to_owned(), result)
+                }
+            })
+            .buffer_unordered(self.concurrency);
+
+        let mut manifest = ChartManifest::default();
+        let mut completed = 0;
+        while let Some((ticker, result)) = results.next().await {
+            completed += 1;
+            on_progress(&ChartProgress { completed, total, ticker: ticker.to_owned(), result: result.clone() });
+            match result {
+                Ok(path) => manifest.succeeded.push((ticker, path)),
+                Err(err) => manifest.failed.push((ticker, err)),
+            }
+        }
+        manifest
+    }
+
+    // helper function to reserve the next start time, and sleep until it is reached
+    async fn wait_for_turn(&self, next_start: &Mutex<Instant>) {
+        let start = {
+            let mut next_start = next_start.lock().await;
+            let start = (*next_start).max(Instant::now());
+            *next_start = start + self.interval;
+            start
+        };
+        tokio::time::sleep_until(start).await;
+    }
+}
+
+/// Returns the tickers of a `TableData`, e.g. the result of `Screener`. The column named "Ticker" in
+/// the header row is used if present, otherwise the first column.
+pub fn tickers_from_table(table: &TableData) -> Vec<String> {
+    let header_index = table.first().and_then(|header| header.iter().position(|h| h == "Ticker"));
+    let (index, skip) = match header_index {
+        Some(index) => (index, 1),
+        None => (0, 0),
+    };
+
+    table.iter()
+        .skip(skip)
+        .filter_map(|row| row.get(index))
+        .filter(|ticker| !ticker.is_empty())
+        .cloned()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_url() {
+        let request = ChartRequest::new();
+        assert_eq!(request.to_url("AAPL"), "https://finviz.com/chart.ashx?t=AAPL&ty=c&ta=0&p=d");
+    }
+
+    #[test]
+    fn test_advanced_url() {
+        let mut request = ChartRequest::new();
+        request.set_chart_type(ChartType::ADVANCED);
+        assert_eq!(request.to_url("AAPL"), "https://finviz.com/chart.ashx?t=AAPL&ty=c&ta=1&p=d");
+        request.set_timeframe(TimeFrameType::Weekly);
+        assert_eq!(request.to_url("AAPL"), "https://finviz.com/chart.ashx?t=AAPL&ty=c&ta=0&p=w");
+    }
+
+    #[test]
+    fn test_intraday_url_with_size() {
+        let mut request = ChartRequest::new();
+        request.set_timeframe(TimeFrameType::Minute15).set_chart_type(ChartType::LINE).set_size(ChartSize::Large);
+        assert_eq!(request.to_url("MSFT"), "https://finviz.com/chart.ashx?t=MSFT&ty=l&ta=0&p=i15&s=l");
+    }
+
+    #[test]
+    fn test_tickers_from_table() {
+        let table: TableData = vec![
+            vec!["Ticker".to_string(), "Company".to_string()],
+            vec!["AAPL".to_string(), "Apple Inc.".to_string()],
+            vec!["MSFT".to_string(), "Microsoft Corporation".to_string()],
+        ];
+        assert_eq!(tickers_from_table(&table), vec!["AAPL", "MSFT"]);
+        assert_eq!(tickers_from_table(&table[1..].to_vec()), vec!["AAPL", "MSFT"]);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_turn() {
+        let mut downloader = ChartDownloader::new(ChartRequest::new());
+        downloader.set_interval(Duration::from_millis(50));
+        let next_start = Mutex::new(Instant::now());
+        let begin = Instant::now();
+        for _ in 0..3 {
+            downloader.wait_for_turn(&next_start).await;
+        }
+        assert!(begin.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_instrument_url() {
+        let chart = InstrumentChart::new(Instrument::Forex, InstrumentTimeFrame::Daily);
+        assert_eq!(chart.to_url("EURUSD"), "https://finviz.com/fx_image.ashx?eurusd_d1_l.png");
+        let chart = InstrumentChart::new(Instrument::Future, InstrumentTimeFrame::Minute5);
+        assert_eq!(chart.to_url("ES"), "https://finviz.com/fut_image.ashx?es_m5_l.png");
+        let chart = InstrumentChart::new(Instrument::Crypto, InstrumentTimeFrame::Monthly);
+        assert_eq!(chart.to_url("BTCUSD"), "https://finviz.com/crypto_image.ashx?btcusd_mo_l.png");
+    }
+
+    #[tokio::test]
+    async fn test_chart_ticker() {
+        let row = ["EURUSD", "1.0856", "0.01%", "-0.05%", "0.32%", "1.10%", "-", "2.00%", "3.00%", "4.00%", "0.50%"]
+            .map(String::from).to_vec();
+        assert_eq!(row.chart_ticker(), "EURUSD");
+        let perf = PerformanceRow::from_row(&row, crate::performance::PerformanceUnit::Percent).unwrap();
+        assert_eq!(perf.chart_ticker(), "EURUSD");
+        assert_eq!("ES".chart_ticker(), "ES");
+
+        let chart = InstrumentChart::new(Instrument::Forex, InstrumentTimeFrame::Daily);
+        assert!(chart.download(&Vec::new(), ".").await.is_err());
+    }
+
+    #[test]
+    fn test_file_name() {
+        let date = NaiveDate::from_ymd_opt(2023, 5, 15).unwrap();
+        let mut request = ChartRequest::new();
+        request.set_timeframe(TimeFrameType::Weekly);
+        assert_eq!(request.render_file_name("AAPL", date), "AAPL_weekly_candle");
+        request.set_file_name_template("{date}/{ticker}-{timeframe}");
+        assert_eq!(request.render_file_name("BRK-B", date), "2023-05-15_BRK-B-weekly");
+    }
+
+    #[test]
+    fn test_overlays_url() {
+        let mut request = ChartRequest::new();
+        request.set_timeframe(TimeFrameType::Hourly)
+            .add_overlay(ChartOverlay::Sma(50))
+            .add_overlay(ChartOverlay::Ema(20))
+            .add_overlay(ChartOverlay::Bollinger(20, 2))
+            .add_overlay(ChartOverlay::Volume);
+        assert_eq!(request.to_url("AAPL"), "https://finviz.com/chart.ashx?t=AAPL&ty=c&ta=sma_50,ema_20,bb_20_2,vol&p=h");
+    }
+}
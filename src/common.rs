@@ -14,6 +14,17 @@ pub type TableData = Vec<Vec<String>>;
 /// Alias to represent key-value String data
 pub type DictData = BTreeMap<String, String>;
 
+/// Convert a typed record into a row of `TableData`, so a `Vec` of records can be written by the
+/// traits in `output`
+pub trait TableRow {
+
+    /// Returns the header matching the columns of `to_row`
+    fn header() -> Vec<String>;
+
+    /// Converts the record into a row of strings
+    fn to_row(&self) -> Vec<String>;
+}
+
 /// Convert the scraping data into result type T 
 #[async_trait]
 pub trait Scrape<T> {
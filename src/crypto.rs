@@ -1,5 +1,8 @@
 use crate::web_scraper::scrape_common;
 use crate::common::{TableData, Scrape};
+use crate::chart::{ChartTicker, Instrument, InstrumentChart, InstrumentTimeFrame};
+use crate::order_type::Ordering;
+use crate::performance::{PerformanceOrder, PerformanceRow, PerformanceUnit};
 use async_trait::async_trait;
 
 /// Represents a Crypto struct.
@@ -25,7 +28,30 @@ use async_trait::async_trait;
 ///
 ///
 /// ````
-pub struct Crypto {}
+///
+/// The typed rows sorted by the performance of the day:
+///
+/// ```
+/// use finviz_rs::{
+///     crypto::Crypto,
+///     order_type::Ordering,
+///     performance::PerformanceOrder,
+/// };
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(),Box<dyn std::error::Error>>{
+///     let rows = Crypto::new()
+///         .set_order(PerformanceOrder::PerfDay, Ordering::Descending)
+///         .rows().await?;
+///     for row in rows.iter().take(3) {
+///         println!("{} {:?}%", row.ticker, row.perf_day);
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct Crypto {
+    order: Option<(PerformanceOrder, Ordering)>,
+}
 
 impl Default for Crypto {
 
@@ -42,7 +68,33 @@ impl Crypto {
 
     /// Creates a new instance of Crypto.
     pub fn new() -> Self {
-        Self{}
+        Self{ order: None }
+    }
+
+    /// Sets the sort order of the table. Only the performance table view of the page is scraped; the
+    /// other views of the page show the charts of the tickers, which are retrieved by `Crypto::chart`.
+    pub fn set_order(&mut self, order: PerformanceOrder, ordering: Ordering) -> &mut Self {
+        self.order = Some((order, ordering));
+        self
+    }
+
+    /// Returns the URL for retrieving crypto performance data.
+    fn get_url(&self) -> String {
+        match self.order {
+            Some((order, ordering)) => format!("{}?v=1&o={}{}", Crypto::BASE_URL, ordering, order),
+            None => Crypto::BASE_URL.to_string(),
+        }
+    }
+
+    /// Scrapes crypto performance data like `scrape`, and converts each row to `PerformanceRow` in percent.
+    pub async fn rows(&self) -> Result<Vec<PerformanceRow>, Box<dyn std::error::Error>> {
+        PerformanceRow::from_table(&self.scrape().await?, PerformanceUnit::Percent)
+    }
+
+    /// Retrieves the chart image of a ticker, or of a row returned by `Crypto::rows` (`PerformanceRow`) or `Crypto::scrape`,
+    /// return the String of the saved image path in the specified output directory on success, or error on failure
+    pub async fn chart<T: ChartTicker + ?Sized>(row: &T, timeframe: InstrumentTimeFrame, out_dir: &str) -> Result<String, Box<dyn std::error::Error>> {
+        InstrumentChart::new(Instrument::Crypto, timeframe).download(row, out_dir).await
     }
 
     /// Returns the default header for crypto performance table.
@@ -66,6 +118,19 @@ impl Scrape<TableData> for Crypto {
     /// A Result containing the scraped data as TableData on success, or a `Box<dyn std::error::Error>`
     /// on failure.
     async fn scrape(&self,) -> Result<TableData, Box<dyn std::error::Error>> {
-        scrape_common(Crypto::BASE_URL, true).await
+        scrape_common(&self.get_url(), true).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_url() {
+        let mut crypto = Crypto::default();
+        assert_eq!(crypto.get_url(), "https://finviz.com/crypto_performance.ashx");
+        crypto.set_order(PerformanceOrder::PerfYearToDate, Ordering::Descending);
+        assert_eq!(crypto.get_url(), "https://finviz.com/crypto_performance.ashx?v=1&o=-perfytdpct");
     }
 }
@@ -1,5 +1,8 @@
 use crate::web_scraper::scrape_common;
 use crate::common::{TableData, Scrape};
+use crate::chart::{ChartTicker, Instrument, InstrumentChart, InstrumentTimeFrame};
+use crate::order_type::Ordering;
+use crate::performance::{PerformanceOrder, PerformanceRow, PerformanceUnit};
 use strum::EnumIter;
 use async_trait::async_trait;
 
@@ -34,8 +37,30 @@ pub enum ForexType {
 /// ```
 ///
 /// The above example demonstrates how to retrive Forex performance data into a table and print it.
+///
+/// The typed rows keep the performance in PIPS apart from percentages:
+///
+/// ```
+/// use finviz_rs::{
+///     forex::{Forex, ForexType},
+///     order_type::Ordering,
+///     performance::PerformanceOrder,
+/// };
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(),Box<dyn std::error::Error>> {
+///     let rows = Forex::new(ForexType::PIPS)
+///         .set_order(PerformanceOrder::PerfWeek, Ordering::Descending)
+///         .rows().await?;
+///     for row in rows.iter().take(3) {
+///         println!("{} {:?} pips", row.ticker, row.perf_week);
+///     }
+///     Ok(())
+/// }
+/// ```
 pub struct Forex {
     forext_type: ForexType,
+    order: Option<(PerformanceOrder, Ordering)>,
 }
 
 impl Default for Forex {
@@ -55,17 +80,51 @@ impl Forex {
     ///
     /// * `forext_type` - The type of Forex data to retrieve.
     pub fn new(forext_type: ForexType) -> Self {
-        Self { forext_type }
+        Self { forext_type, order: None }
+    }
+
+    /// Sets the sort order of the table. Only the performance table view of the page is scraped; the
+    /// other views of the page show the charts of the tickers, which are retrieved by `Forex::chart`.
+    pub fn set_order(&mut self, order: PerformanceOrder, ordering: Ordering) -> &mut Self {
+        self.order = Some((order, ordering));
+        self
+    }
+
+    /// Returns the unit of the performance of the ForexType.
+    pub fn unit(&self) -> PerformanceUnit {
+        match self.forext_type {
+            ForexType::Percent => PerformanceUnit::Percent,
+            ForexType::PIPS => PerformanceUnit::Pips,
+        }
     }
 
     /// Returns the URL for retrieving Forex performance data based on the ForexType.
     fn get_url(&self) -> String {
-        match self.forext_type {
-            ForexType::Percent => Forex::BASE_URL.to_string(),
-            ForexType::PIPS => format!("{}?v=1&tv=2&o=-perfdaypct", Forex::BASE_URL),
+        match (self.forext_type, self.order) {
+            (ForexType::Percent, None) => Forex::BASE_URL.to_string(),
+            (ForexType::PIPS, None) => format!("{}?v=1&tv=2&o=-perfdaypct", Forex::BASE_URL),
+            (forex_type, Some((order, ordering))) => {
+                let tv = match forex_type {
+                    ForexType::Percent => 1,
+                    ForexType::PIPS => 2,
+                };
+                format!("{}?v=1&tv={}&o={}{}", Forex::BASE_URL, tv, ordering, order)
+            },
         }
     }
 
+    /// Scrapes Forex performance data like `scrape`, and converts each row to `PerformanceRow` in the
+    /// unit of the ForexType.
+    pub async fn rows(&self) -> Result<Vec<PerformanceRow>, Box<dyn std::error::Error>> {
+        PerformanceRow::from_table(&self.scrape().await?, self.unit())
+    }
+
+    /// Retrieves the chart image of a ticker, or of a row returned by `Forex::rows` (`PerformanceRow`) or `Forex::scrape`,
+    /// return the String of the saved image path in the specified output directory on success, or error on failure
+    pub async fn chart<T: ChartTicker + ?Sized>(row: &T, timeframe: InstrumentTimeFrame, out_dir: &str) -> Result<String, Box<dyn std::error::Error>> {
+        InstrumentChart::new(Instrument::Forex, timeframe).download(row, out_dir).await
+    }
+
     /// Returns the default header for Forex performance table.
     ///
     /// # Returns
@@ -103,7 +162,17 @@ mod tests {
         let forex = Forex::new(ForexType::PIPS);
         assert_eq!(forex.get_url(), "https://finviz.com/forex_performance.ashx?v=1&tv=2&o=-perfdaypct".to_string())
     }
-}
-
 
+    #[test]
+    fn test_url_with_order() {
+        let mut forex = Forex::default();
+        forex.set_order(PerformanceOrder::PerfWeek, Ordering::Descending);
+        assert_eq!(forex.get_url(), "https://finviz.com/forex_performance.ashx?v=1&tv=1&o=-perfweekpct");
+        assert_eq!(forex.unit(), PerformanceUnit::Percent);
 
+        let mut forex = Forex::new(ForexType::PIPS);
+        forex.set_order(PerformanceOrder::Ticker, Ordering::Ascending);
+        assert_eq!(forex.get_url(), "https://finviz.com/forex_performance.ashx?v=1&tv=2&o=ticker");
+        assert_eq!(forex.unit(), PerformanceUnit::Pips);
+    }
+}
@@ -1,6 +1,9 @@
 use crate::web_scraper::get_html_body;
-use crate::common::{TableData, Scrape};
-use serde_json::Value;
+use crate::common::{TableData, Scrape, TableRow};
+use crate::chart::{ChartTicker, Instrument, InstrumentChart, InstrumentTimeFrame};
+use crate::parse::parse_number;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
 use std::fmt;
 use strum::EnumIter;
 use async_trait::async_trait;
@@ -12,13 +15,19 @@ type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 enum FutureError {
     /// Indicates that the expected JSON value is not an array.
     NoJsonArray,
+    /// Indicates that the page does not contain the marker around the JSON data, e.g. after a layout change.
+    MissingMarker(&'static str),
+    /// Indicates that a JSON record is not an object.
+    NoJsonObject,
 }
 
 impl fmt::Display for FutureError {
     /// Formats the error message associated with the FutureError.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
          match *self {
-            FutureError::NoJsonArray =>  write!(f, "expect json array, but failed to convert it via as_array")
+            FutureError::NoJsonArray =>  write!(f, "expect json array, but failed to convert it via as_array"),
+            FutureError::MissingMarker(marker) => write!(f, "expect `{}` in the futures page, but it is not found", marker),
+            FutureError::NoJsonObject => write!(f, "expect json object for each future, but failed to convert it via as_object"),
          }
     }
 }
@@ -27,11 +36,88 @@ impl std::error::Error for FutureError {
     /// Retrieves the underlying source of the error, if any.
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match *self {
-            FutureError::NoJsonArray => None,
+            FutureError::NoJsonArray | FutureError::MissingMarker(_) | FutureError::NoJsonObject => None,
         }
     }
 }
 
+/// A typed record of the futures performance, returned by `Future::quotes`
+#[derive(Clone, Debug, PartialEq)]
+pub struct FutureQuote {
+    /// ticker of the future, e.g. "ES"
+    pub ticker: String,
+    /// name of the future, e.g. "S&P 500"
+    pub label: String,
+    /// group of the future, e.g. "INDICES" or "ENERGY"
+    pub group: String,
+    /// performance of the timeframe in percent
+    pub perf: Option<f64>,
+    /// latest price, if present in the record
+    pub price: Option<f64>,
+    /// previous close, if present in the record
+    pub prev_close: Option<f64>,
+    /// the other fields of the record as they are
+    pub extra: Map<String, Value>,
+}
+
+impl FutureQuote {
+
+    /// Converts a JSON record of the futures page into `FutureQuote`.
+    pub fn from_json(record: &Value) -> Result<Self> {
+        let mut extra = record.as_object().ok_or(FutureError::NoJsonObject)?.clone();
+        let mut take_string = |keys: &[&str]| keys.iter().find_map(|key| extra.remove(*key)).map(|value| json_string(&value)).unwrap_or_default();
+        let (ticker, label, group) = (take_string(&["ticker"]), take_string(&["label"]), take_string(&["group"]));
+        let mut take_number = |keys: &[&str]| keys.iter().find_map(|key| extra.remove(*key)).and_then(|value| json_number(&value));
+
+        Ok(FutureQuote {
+            ticker,
+            label,
+            group,
+            perf: take_number(&["perf"]),
+            price: take_number(&["price", "last"]),
+            prev_close: take_number(&["prevClose", "prev_close"]),
+            extra,
+        })
+    }
+}
+
+impl TableRow for FutureQuote {
+
+    fn header() -> Vec<String> {
+        ["Ticker", "Label", "Group", "Perf", "Price", "Prev Close"].map(String::from).to_vec()
+    }
+
+    fn to_row(&self) -> Vec<String> {
+        let optional = |value: Option<f64>| value.map_or("-".to_string(), |v| v.to_string());
+        vec![
+            self.ticker.to_owned(),
+            self.label.to_owned(),
+            self.group.to_owned(),
+            optional(self.perf),
+            optional(self.price),
+            optional(self.prev_close),
+        ]
+    }
+}
+
+// helper function to convert a JSON value to String, without the double quotes of a JSON string
+fn json_string(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.to_owned(),
+        Value::Null => String::new(),
+        value => value.to_string(),
+    }
+}
+
+// helper function to convert a JSON number, or a string of a number, to f64
+fn json_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => parse_number(s),
+        _ => None,
+    }
+}
+
 /// Represents the time frame for futures data.
 #[doc(hidden)]
 #[derive(Clone, Copy, EnumIter)]
@@ -62,6 +148,20 @@ pub enum TimeFrame {
 ///
 ///
 /// The above example demonstrates how to retrive futures data into a table and print it.
+///
+/// The typed quotes keep all the fields of the records, and can be grouped:
+///
+/// ```
+/// use finviz_rs::future::Future;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(),Box<dyn std::error::Error>>{
+///     for (group, quotes) in Future::default().quotes_by_group().await? {
+///         println!("{}: {:?}", group, quotes.iter().map(|q| (&q.ticker, q.perf)).collect::<Vec<_>>());
+///     }
+///     Ok(())
+/// }
+/// ```
 pub struct Future {
     timeframe: TimeFrame,
 }
@@ -101,6 +201,12 @@ impl Future {
     }
 
 
+    /// Retrieves the chart image of a ticker, or of a row returned by `Future::quotes` (`FutureQuote`) or `Future::scrape`,
+    /// return the String of the saved image path in the specified output directory on success, or error on failure
+    pub async fn chart<T: ChartTicker + ?Sized>(row: &T, timeframe: InstrumentTimeFrame, out_dir: &str) -> Result<String> {
+        InstrumentChart::new(Instrument::Future, timeframe).download(row, out_dir).await
+    }
+
     /// Returns the default header for the futures performance table.
     ///
     /// # Returns
@@ -110,6 +216,43 @@ impl Future {
         ["ticker", "label", "group", "perf"]
         .map(String::from).to_vec()
     }
+
+    /// Scrapes the futures performance data into `FutureQuote` with all the fields of the records.
+    pub async fn quotes(&self) -> Result<Vec<FutureQuote>> {
+        let body = get_html_body(&self.get_url()).await?;
+        parse_records(&body)?.iter().map(FutureQuote::from_json).collect()
+    }
+
+    /// Scrapes the futures performance data like `quotes`, grouped by the group of the futures, e.g.
+    /// "INDICES", "ENERGY" or "METALS".
+    pub async fn quotes_by_group(&self) -> Result<BTreeMap<String, Vec<FutureQuote>>> {
+        Ok(group_quotes(self.quotes().await?))
+    }
+}
+
+/// Groups the quotes by their group, keeping the order of the quotes in each group.
+pub fn group_quotes(quotes: Vec<FutureQuote>) -> BTreeMap<String, Vec<FutureQuote>> {
+    let mut groups: BTreeMap<String, Vec<FutureQuote>> = BTreeMap::new();
+    for quote in quotes {
+        groups.entry(quote.group.to_owned()).or_default().push(quote);
+    }
+    groups
+}
+
+// helper function to extract the JSON records embedded in the futures page as `var rows = [...];`
+fn parse_records(body: &str) -> Result<Vec<Value>> {
+    const START_MARKER: &str = "var rows = ";
+    const END_MARKER: &str = "FinvizInitFuturesPerformance(rows);";
+
+    let start_index = body.find(START_MARKER).ok_or(FutureError::MissingMarker(START_MARKER))? + START_MARKER.len();
+    let end_index = body[start_index..].find(END_MARKER).ok_or(FutureError::MissingMarker(END_MARKER))? + start_index;
+    let data_str = body[start_index..end_index].trim().trim_end_matches(';');
+    let data: Value = serde_json::from_str(data_str)?;
+
+    match data {
+        Value::Array(rows) => Ok(rows),
+        _ => Err(Box::new(FutureError::NoJsonArray)),
+    }
 }
 
 #[async_trait]
@@ -123,36 +266,18 @@ impl Scrape<TableData> for Future {
     async fn scrape(&self) -> Result<TableData> {
         let url = self.get_url();
         let body = get_html_body(&url).await?;
-        let start_index = body.find("var rows = ").unwrap() + 11;
-        let end_index = body.find("FinvizInitFuturesPerformance(rows);").unwrap();
-        let data_str = body[start_index..end_index].trim().trim_end_matches(';');
-        let data: Value = serde_json::from_str(data_str)?;
-
-        let mut result = Vec::new();
+        let rows = parse_records(&body)?;
         let expected_keys = Future::default_header();
 
-        if let Some(rows) = data.as_array() {
-            for row in rows {
-              let row_values: Vec<String> = expected_keys
+        let result = rows.iter()
+            .map(|row| {
+                expected_keys
                     .iter()
-                    .map(|key| {
-                        if let Some(value) = row.get(key) {
-                            if value.is_string() {
-                                // remove double quotes if Value is String type
-                                value.as_str().unwrap().to_string()
-                            } else {
-                                value.to_string()
-                            }
-                        } else {
-                            String::new()
-                        }
-                    })
-                    .collect();
-                result.push(row_values);
-            }
-            return Ok(result);
-        } 
-        Err(Box::new(FutureError::NoJsonArray))
+                    .map(|key| row.get(key).map(json_string).unwrap_or_default())
+                    .collect()
+            })
+            .collect();
+        Ok(result)
     }
 
 }
@@ -166,6 +291,41 @@ mod tests {
         let future = Future::new(TimeFrame::Quarter);
         assert_eq!(future.get_url(), "https://finviz.com/futures_performance.ashx?v=14".to_string())
     }
+
+    const BODY: &str = r#"<script>
+        var rows = [{"label":"S&P 500","ticker":"ES","group":"INDICES","perf":0.25,"prevClose":4123.5,"last":"4,133.75","sparkline":[1,2]},
+                    {"label":"Crude Oil WTI","ticker":"CL","group":"ENERGY","perf":-1.2},
+                    {"label":"Nasdaq 100","ticker":"NQ","group":"INDICES","perf":"0.80"}];
+        FinvizInitFuturesPerformance(rows);
+    </script>"#;
+
+    #[test]
+    fn test_parse_quotes() {
+        let quotes = parse_records(BODY).unwrap().iter().map(FutureQuote::from_json).collect::<Result<Vec<_>>>().unwrap();
+        assert_eq!(quotes.len(), 3);
+        assert_eq!(quotes[0].ticker, "ES");
+        assert_eq!(quotes[0].label, "S&P 500");
+        assert_eq!(quotes[0].perf, Some(0.25));
+        assert_eq!(quotes[0].price, Some(4133.75));
+        assert_eq!(quotes[0].prev_close, Some(4123.5));
+        assert_eq!(quotes[0].extra.keys().collect::<Vec<_>>(), vec!["sparkline"]);
+        assert_eq!(quotes[1].price, None);
+        assert_eq!(quotes[2].perf, Some(0.8));
+
+        let groups = group_quotes(quotes);
+        assert_eq!(groups.keys().collect::<Vec<_>>(), vec!["ENERGY", "INDICES"]);
+        assert_eq!(groups["INDICES"].iter().map(|q| q.ticker.as_str()).collect::<Vec<_>>(), vec!["ES", "NQ"]);
+    }
+
+    #[test]
+    fn test_missing_marker() {
+        let err = parse_records("<html>new layout</html>").unwrap_err();
+        assert_eq!(err.to_string(), "expect `var rows = ` in the futures page, but it is not found");
+        let err = parse_records("var rows = [];").unwrap_err();
+        assert!(err.to_string().contains("FinvizInitFuturesPerformance"));
+        assert!(parse_records("var rows = {}; FinvizInitFuturesPerformance(rows);").is_err());
+        assert!(FutureQuote::from_json(&Value::Null).is_err());
+    }
 }
 
 
@@ -1,5 +1,9 @@
 use crate::web_scraper::scrape_common;
-use crate::common::{TableData, Scrape};
+use crate::common::{TableData, Scrape, TableRow};
+use crate::parse::{parse_count, parse_number};
+use crate::screener::Screener;
+use crate::screener_type::ScreenerType;
+use crate::web_scraper::{get_chart_image, save_image};
 use std::fmt;
 use strum::EnumIter;
 use async_trait::async_trait;
@@ -96,6 +100,138 @@ impl fmt::Display for GroupType {
     }
 }
 
+/// Represents the columns of `GroupType::Custom` selected by `Group::set_columns`
+#[derive(Clone, Copy, Debug, PartialEq, EnumIter)]
+pub enum GroupColumn {
+    /// row number, which is always requested first
+    No,
+    /// name of the group
+    Name,
+    /// market capitalization
+    MarketCap,
+    /// price to earnings
+    PriceEarnings,
+    /// forward price to earnings
+    ForwardPriceEarnings,
+    /// price to earnings to growth
+    PEG,
+    /// price to sales
+    PriceSales,
+    /// price to book
+    PriceBook,
+    /// price to cash per share
+    PriceCash,
+    /// price to free cash flow
+    PriceFreeCashFlow,
+    /// dividend yield
+    DividendYield,
+    /// EPS growth of the past 5 years
+    EPSGrowthPast5Years,
+    /// estimated EPS growth of the next 5 years
+    EPSGrowthNext5Years,
+    /// sales growth of the past 5 years
+    SalesGrowthPast5Years,
+    /// short interest of the float
+    FloatShort,
+    /// performance of the week
+    PerformanceWeek,
+    /// performance of the month
+    PerformanceMonth,
+    /// performance of the quarter
+    PerformanceQuarter,
+    /// performance of the half year
+    PerformanceHalfYear,
+    /// performance of the year
+    PerformanceYear,
+    /// performance of the year to date
+    PerformanceYearToDate,
+    /// analyst recommendation
+    AnalystRecommendation,
+    /// average volume of 3 months
+    AverageVolume,
+    /// relative volume
+    RelativeVolume,
+    /// change of today
+    Change,
+    /// volume of today
+    Volume,
+    /// number of stocks
+    NumberOfStocks,
+}
+
+impl GroupColumn {
+
+    /// Returns the id of the column used in the `c` URL parameter.
+    pub fn id(self) -> u32 {
+        match self {
+            GroupColumn::No => 0,
+            GroupColumn::Name => 1,
+            GroupColumn::MarketCap => 2,
+            GroupColumn::PriceEarnings => 3,
+            GroupColumn::ForwardPriceEarnings => 4,
+            GroupColumn::PEG => 5,
+            GroupColumn::PriceSales => 6,
+            GroupColumn::PriceBook => 7,
+            GroupColumn::PriceCash => 8,
+            GroupColumn::PriceFreeCashFlow => 9,
+            GroupColumn::DividendYield => 10,
+            GroupColumn::EPSGrowthPast5Years => 11,
+            GroupColumn::EPSGrowthNext5Years => 12,
+            GroupColumn::SalesGrowthPast5Years => 13,
+            GroupColumn::FloatShort => 14,
+            GroupColumn::PerformanceWeek => 15,
+            GroupColumn::PerformanceMonth => 16,
+            GroupColumn::PerformanceQuarter => 17,
+            GroupColumn::PerformanceHalfYear => 18,
+            GroupColumn::PerformanceYear => 19,
+            GroupColumn::PerformanceYearToDate => 20,
+            GroupColumn::AnalystRecommendation => 21,
+            GroupColumn::AverageVolume => 22,
+            GroupColumn::RelativeVolume => 23,
+            GroupColumn::Change => 24,
+            GroupColumn::Volume => 25,
+            GroupColumn::NumberOfStocks => 26,
+        }
+    }
+}
+
+impl fmt::Display for GroupColumn {
+
+    /// Formats the GroupColumn enum as the name of the column on finviz.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            GroupColumn::No => "No.",
+            GroupColumn::Name => "Name",
+            GroupColumn::MarketCap => "Market Cap",
+            GroupColumn::PriceEarnings => "P/E",
+            GroupColumn::ForwardPriceEarnings => "Fwd P/E",
+            GroupColumn::PEG => "PEG",
+            GroupColumn::PriceSales => "P/S",
+            GroupColumn::PriceBook => "P/B",
+            GroupColumn::PriceCash => "P/C",
+            GroupColumn::PriceFreeCashFlow => "P/FCF",
+            GroupColumn::DividendYield => "Dividend",
+            GroupColumn::EPSGrowthPast5Years => "EPS past 5Y",
+            GroupColumn::EPSGrowthNext5Years => "EPS next 5Y",
+            GroupColumn::SalesGrowthPast5Years => "Sales past 5Y",
+            GroupColumn::FloatShort => "Float Short",
+            GroupColumn::PerformanceWeek => "Perf Week",
+            GroupColumn::PerformanceMonth => "Perf Month",
+            GroupColumn::PerformanceQuarter => "Perf Quart",
+            GroupColumn::PerformanceHalfYear => "Perf Half",
+            GroupColumn::PerformanceYear => "Perf Year",
+            GroupColumn::PerformanceYearToDate => "Perf YTD",
+            GroupColumn::AnalystRecommendation => "Recom",
+            GroupColumn::AverageVolume => "Avg Volume",
+            GroupColumn::RelativeVolume => "Rel Volume",
+            GroupColumn::Change => "Change",
+            GroupColumn::Volume => "Volume",
+            GroupColumn::NumberOfStocks => "Stocks",
+        };
+        write!(f, "{}", value)
+    }
+}
+
 #[doc(hidden)]
 #[allow(dead_code)]
 #[derive(Clone, Copy, EnumIter)]
@@ -140,6 +276,69 @@ impl fmt::Display for GroupBy {
     }
 }
 
+impl GroupBy {
+
+    // helper function to return the name of the group used in the chart images, e.g. "sector" or
+    // "industry_technology"
+    fn chart_name(&self) -> String {
+        self.to_string()
+            .split('&')
+            .filter_map(|param| param.split_once('=').map(|(_, value)| value))
+            .collect::<Vec<_>>()
+            .join("_")
+    }
+
+    /// Returns the screener filter of a group by its name in the result, e.g. "sec_technology" for
+    /// the sector "Technology", "ind_semiconductors" for the industry "Semiconductors", "geo_usa" for
+    /// the country "USA", or "cap_large" for the capitalization "Large Cap".
+    pub fn screener_filter(&self, name: &str) -> String {
+        let slug = name.chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_lowercase();
+
+        match self {
+            GroupBy::Sector => format!("sec_{}", slug),
+            GroupBy::Country => format!("geo_{}", slug),
+            GroupBy::Capitalization => format!("cap_{}", slug.strip_suffix("cap").unwrap_or(&slug)),
+            _ => format!("ind_{}", slug),
+        }
+    }
+}
+
+/// Represents the timeframe of the performance shown in the chart of `Group::chart`
+#[derive(Clone, Copy, Debug, PartialEq, EnumIter)]
+pub enum GroupChartTimeFrame {
+    /// performance of today
+    Day,
+    /// performance of the week
+    Week,
+    /// performance of the month
+    Month,
+    /// performance of the quarter
+    Quarter,
+    /// performance of the half year
+    HalfYear,
+    /// performance of the year
+    Year,
+}
+
+impl fmt::Display for GroupChartTimeFrame {
+
+    /// Formats the GroupChartTimeFrame enum as a string to be used in the chart URL.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            GroupChartTimeFrame::Day => "t",
+            GroupChartTimeFrame::Week => "w",
+            GroupChartTimeFrame::Month => "m",
+            GroupChartTimeFrame::Quarter => "q",
+            GroupChartTimeFrame::HalfYear => "h",
+            GroupChartTimeFrame::Year => "y",
+        };
+        write!(f, "{}", value)
+    }
+}
+
 #[doc(hidden)]
 #[allow(dead_code)]
 #[derive(Clone, Copy, EnumIter)]
@@ -189,11 +388,87 @@ impl fmt::Display for Ordering {
 /// ```
 ///
 /// The above example demonstrates how to retrive Group type of data into a table and print it.
+///
+/// The table can be converted into the typed rows of the view, which look up the columns by name:
+///
+/// ```
+/// use finviz_rs::{
+///     group::*,
+///     common::Scrape,
+/// };
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(),Box<dyn std::error::Error>>{
+///     let table = Group::new(GroupBy::Sector, GroupType::Performance, OrderBy::PerformanceWeek, Ordering::Descending)
+///         .scrape().await?;
+///     for row in GroupPerformanceRow::from_table(&table)? {
+///         println!("{}: {:?}", row.name, row.perf_week);
+///     }
+///     Ok(())
+/// }
+/// ```
+///
+/// The stocks of a group are screened by `constituents`, e.g. the best industry of the week:
+///
+/// ```
+/// use finviz_rs::{
+///     group::*,
+///     output::ToTable,
+///     common::Scrape,
+/// };
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(),Box<dyn std::error::Error>>{
+///     let group = Group::new(GroupBy::Industry, GroupType::Performance, OrderBy::PerformanceWeek, Ordering::Descending);
+///     let rows = GroupPerformanceRow::from_table(&group.scrape().await?)?;
+///     if let Some(best) = rows.first() {
+///         let stocks = group.constituents(&best.name).scrape().await?;
+///         println!("{}", stocks.to_table(None, Some(5)));
+///     }
+///     Ok(())
+/// }
+/// ```
+///
+/// The bar chart of the performance of the groups is downloaded by `chart`:
+///
+/// ```
+/// use finviz_rs::group::*;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(),Box<dyn std::error::Error>>{
+///     let path = Group::default().chart(GroupChartTimeFrame::Week, "charts").await?;
+///     println!("{}", path);
+///     Ok(())
+/// }
+/// ```
+///
+/// The columns are selected by `set_columns`, which switches to `GroupType::Custom`:
+///
+/// ```
+/// use finviz_rs::{
+///     group::*,
+///     output::ToTable,
+///     common::Scrape,
+/// };
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(),Box<dyn std::error::Error>>{
+///     let table_str = Group::default()
+///         .set_columns(&[GroupColumn::Name, GroupColumn::PerformanceWeek, GroupColumn::PriceEarnings])
+///         .scrape().await?
+///         .to_table(None, Some(5));
+///     println!("{}", table_str);
+///     Ok(())
+/// }
+/// ```
 pub struct Group {
     group_by: GroupBy,
     group_type: GroupType,
     order_by: OrderBy,
     ordering: Ordering,
+    columns: Vec<GroupColumn>,
+    // the view to restore when the custom columns are cleared
+    view: GroupType,
 }
 
 impl Default for Group {
@@ -207,13 +482,62 @@ impl Group {
 
     /// Creates a new Group instance with the specified parameters.
     pub fn new(group_by: GroupBy, group_type: GroupType, order_by: OrderBy, ordering: Ordering) -> Self {
-        Self{group_by, group_type, order_by, ordering}
+        let view = match group_type {
+            GroupType::Custom => GroupType::Overview,
+            view => view,
+        };
+        Self{group_by, group_type, order_by, ordering, columns: Vec::new(), view}
+    }
+
+    /// Generates the URL of the bar chart of the performance of the groups in the timeframe.
+    pub fn chart_url(&self, timeframe: GroupChartTimeFrame) -> String {
+        format!("https://finviz.com/grp_image.ashx?bar_{}_{}.png", self.group_by.chart_name(), timeframe)
+    }
+
+    /// Retrieves the bar chart of the performance of the groups in the timeframe, return the String
+    /// of the saved image path `{out_dir}/{group}_{timeframe}.{extension}` on success, or error on failure.
+    pub async fn chart(&self, timeframe: GroupChartTimeFrame, out_dir: &str) -> Result<String, Box<dyn std::error::Error>> {
+        let image = get_chart_image(&self.chart_url(timeframe)).await?;
+        save_image(&image, out_dir, &format!("{}_{}", self.group_by.chart_name(), timeframe))
+    }
+
+    /// Returns a `Screener` of the overview of the stocks in the group of the result by its name, e.g.
+    /// "Semiconductors" of `GroupBy::Industry`.
+    pub fn constituents(&self, name: &str) -> Screener {
+        let mut screener = Screener::new(ScreenerType::Overview);
+        screener.add_filter(&self.group_by.screener_filter(name));
+        screener
+    }
+
+    /// Selects the columns of the result in order, and switches to `GroupType::Custom`. The header
+    /// row of the result has the names of the columns. The No. column is always requested first,
+    /// and it is dropped from the result like in the other views. Without any other column, it falls
+    /// back to the view the group was created with, e.g. `GroupType::Performance`.
+    pub fn set_columns(&mut self, columns: &[GroupColumn]) -> &mut Self {
+        self.columns = columns.iter()
+            .filter(|column| **column != GroupColumn::No)
+            .fold(Vec::new(), |mut columns, column| {
+                if !columns.contains(column) {
+                    columns.push(*column);
+                }
+                columns
+            });
+        self.group_type = if self.columns.is_empty() { self.view } else { GroupType::Custom };
+        self
     }
 
     /// Constructs the URL for the group with the specified parameters.
     fn get_url(&self) -> String {
+        let columns_url = if self.columns.is_empty() {
+            String::new()
+        } else {
+            let ids = std::iter::once(GroupColumn::No).chain(self.columns.iter().copied())
+                .map(|column| column.id().to_string())
+                .collect::<Vec<_>>();
+            format!("&c={}", ids.join(","))
+        };
         let order_by_url = format!("&o={}{}", self.ordering, self.order_by);
-        format!("{}?{}{}{}", Group::BASE_URL, self.group_by, self.group_type, order_by_url)
+        format!("{}?{}{}{}{}", Group::BASE_URL, self.group_by, self.group_type, columns_url, order_by_url)
     }
 }
 
@@ -228,9 +552,263 @@ impl Scrape<TableData> for Group {
 
 }
 
+// helper struct to look up the cells of a row by the header names of `Group::scrape`
+struct GroupColumns<'a> {
+    header: &'a [String],
+    row: &'a [String],
+}
+
+impl<'a> GroupColumns<'a> {
+
+    // returns the cell of the first of the header names found in the header
+    fn cell(&self, names: &[&str]) -> Option<&'a str> {
+        names.iter()
+            .find_map(|name| self.header.iter().position(|h| h.eq_ignore_ascii_case(name)))
+            .and_then(|index| self.row.get(index))
+            .map(String::as_str)
+    }
+
+    fn number(&self, names: &[&str]) -> Option<f64> {
+        self.cell(names).and_then(parse_number)
+    }
+
+    fn count(&self, names: &[&str]) -> Option<u64> {
+        self.cell(names).and_then(parse_count)
+    }
+}
+
+// helper function to convert the rows of `Group::scrape` by the header in the first row
+fn rows_from_table<T>(table: &TableData, from_columns: fn(&GroupColumns) -> Option<T>) -> Result<Vec<T>, Box<dyn std::error::Error>> {
+    let header = table.first().ok_or("Missing the header of the group table")?;
+    if !header.iter().any(|h| h == "Name") {
+        return Err(format!("Missing the Name column in the header of the group table: {:?}", header).into());
+    }
+
+    Ok(table.iter()
+        .skip(1)
+        .filter_map(|row| from_columns(&GroupColumns { header, row }))
+        .collect())
+}
+
+// helper function to format an optional number of a typed row
+fn format_number(value: Option<f64>) -> String {
+    value.map_or("-".to_string(), |v| v.to_string())
+}
+
+/// A typed row of `Group::scrape` with `GroupType::Overview`. The percentages are kept as numbers,
+/// e.g. 1.5 for "1.50%", and missing values are `None`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GroupOverviewRow {
+    /// name of the group
+    pub name: String,
+    /// number of stocks in the group
+    pub stocks: Option<u64>,
+    /// market capitalization in dollars
+    pub market_cap: Option<f64>,
+    /// dividend yield in percent
+    pub dividend_yield: Option<f64>,
+    /// price to earnings
+    pub pe: Option<f64>,
+    /// forward price to earnings
+    pub forward_pe: Option<f64>,
+    /// price to earnings to growth
+    pub peg: Option<f64>,
+    /// short interest of the float in percent
+    pub float_short: Option<f64>,
+    /// change of today in percent
+    pub change: Option<f64>,
+    /// volume of today
+    pub volume: Option<f64>,
+}
+
+impl GroupOverviewRow {
+
+    /// Converts the result of `Group::scrape` with `GroupType::Overview`, looking up the columns by
+    /// the header names instead of the positions.
+    pub fn from_table(table: &TableData) -> Result<Vec<Self>, Box<dyn std::error::Error>> {
+        rows_from_table(table, |c| Some(GroupOverviewRow {
+            name: c.cell(&["Name"])?.to_string(),
+            stocks: c.count(&["Stocks"]),
+            market_cap: c.number(&["Market Cap"]),
+            dividend_yield: c.number(&["Dividend", "Dividend Yield"]),
+            pe: c.number(&["P/E"]),
+            forward_pe: c.number(&["Fwd P/E", "Forward P/E"]),
+            peg: c.number(&["PEG"]),
+            float_short: c.number(&["Float Short"]),
+            change: c.number(&["Change"]),
+            volume: c.number(&["Volume"]),
+        }))
+    }
+}
+
+impl TableRow for GroupOverviewRow {
+
+    fn header() -> Vec<String> {
+        ["Name", "Stocks", "Market Cap", "Dividend", "P/E", "Fwd P/E", "PEG", "Float Short", "Change", "Volume"]
+            .map(String::from).to_vec()
+    }
+
+    fn to_row(&self) -> Vec<String> {
+        vec![
+            self.name.to_owned(),
+            self.stocks.map_or("-".to_string(), |v| v.to_string()),
+            format_number(self.market_cap),
+            format_number(self.dividend_yield),
+            format_number(self.pe),
+            format_number(self.forward_pe),
+            format_number(self.peg),
+            format_number(self.float_short),
+            format_number(self.change),
+            format_number(self.volume),
+        ]
+    }
+}
+
+/// A typed row of `Group::scrape` with `GroupType::Valuation`. The percentages are kept as numbers,
+/// e.g. 1.5 for "1.50%", and missing values are `None`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GroupValuationRow {
+    /// name of the group
+    pub name: String,
+    /// market capitalization in dollars
+    pub market_cap: Option<f64>,
+    /// price to earnings
+    pub pe: Option<f64>,
+    /// forward price to earnings
+    pub forward_pe: Option<f64>,
+    /// price to earnings to growth
+    pub peg: Option<f64>,
+    /// price to sales
+    pub ps: Option<f64>,
+    /// price to book
+    pub pb: Option<f64>,
+    /// price to cash per share
+    pub pc: Option<f64>,
+    /// price to free cash flow
+    pub pfcf: Option<f64>,
+    /// EPS growth of the past 5 years in percent
+    pub eps_past_5y: Option<f64>,
+    /// estimated EPS growth of the next 5 years in percent
+    pub eps_next_5y: Option<f64>,
+    /// sales growth of the past 5 years in percent
+    pub sales_past_5y: Option<f64>,
+    /// change of today in percent
+    pub change: Option<f64>,
+    /// volume of today
+    pub volume: Option<f64>,
+}
+
+impl GroupValuationRow {
+
+    /// Converts the result of `Group::scrape` with `GroupType::Valuation`, looking up the columns by
+    /// the header names instead of the positions.
+    pub fn from_table(table: &TableData) -> Result<Vec<Self>, Box<dyn std::error::Error>> {
+        rows_from_table(table, |c| Some(GroupValuationRow {
+            name: c.cell(&["Name"])?.to_string(),
+            market_cap: c.number(&["Market Cap"]),
+            pe: c.number(&["P/E"]),
+            forward_pe: c.number(&["Fwd P/E", "Forward P/E"]),
+            peg: c.number(&["PEG"]),
+            ps: c.number(&["P/S"]),
+            pb: c.number(&["P/B"]),
+            pc: c.number(&["P/C"]),
+            pfcf: c.number(&["P/FCF"]),
+            eps_past_5y: c.number(&["EPS past 5Y", "EPS Past 5Y"]),
+            eps_next_5y: c.number(&["EPS next 5Y", "EPS Next 5Y"]),
+            sales_past_5y: c.number(&["Sales past 5Y", "Sales Past 5Y"]),
+            change: c.number(&["Change"]),
+            volume: c.number(&["Volume"]),
+        }))
+    }
+}
+
+impl TableRow for GroupValuationRow {
+
+    fn header() -> Vec<String> {
+        ["Name", "Market Cap", "P/E", "Fwd P/E", "PEG", "P/S", "P/B", "P/C", "P/FCF", "EPS past 5Y", "EPS next 5Y", "Sales past 5Y", "Change", "Volume"]
+            .map(String::from).to_vec()
+    }
+
+    fn to_row(&self) -> Vec<String> {
+        let mut row = vec![self.name.to_owned()];
+        row.extend([
+            self.market_cap, self.pe, self.forward_pe, self.peg, self.ps, self.pb, self.pc, self.pfcf,
+            self.eps_past_5y, self.eps_next_5y, self.sales_past_5y, self.change, self.volume,
+        ].map(format_number));
+        row
+    }
+}
+
+/// A typed row of `Group::scrape` with `GroupType::Performance`. The percentages are kept as numbers,
+/// e.g. 1.5 for "1.50%", and missing values are `None`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GroupPerformanceRow {
+    /// name of the group
+    pub name: String,
+    /// performance of the week in percent
+    pub perf_week: Option<f64>,
+    /// performance of the month in percent
+    pub perf_month: Option<f64>,
+    /// performance of the quarter in percent
+    pub perf_quarter: Option<f64>,
+    /// performance of the half year in percent
+    pub perf_half_year: Option<f64>,
+    /// performance of the year in percent
+    pub perf_year: Option<f64>,
+    /// performance of the year to date in percent
+    pub perf_ytd: Option<f64>,
+    /// average volume of 3 months
+    pub avg_volume: Option<f64>,
+    /// volume of today relative to the average volume
+    pub rel_volume: Option<f64>,
+    /// change of today in percent
+    pub change: Option<f64>,
+    /// volume of today
+    pub volume: Option<f64>,
+}
+
+impl GroupPerformanceRow {
+
+    /// Converts the result of `Group::scrape` with `GroupType::Performance`, looking up the columns
+    /// by the header names instead of the positions.
+    pub fn from_table(table: &TableData) -> Result<Vec<Self>, Box<dyn std::error::Error>> {
+        rows_from_table(table, |c| Some(GroupPerformanceRow {
+            name: c.cell(&["Name"])?.to_string(),
+            perf_week: c.number(&["Perf Week"]),
+            perf_month: c.number(&["Perf Month"]),
+            perf_quarter: c.number(&["Perf Quart", "Perf Quarter"]),
+            perf_half_year: c.number(&["Perf Half", "Perf Half Y"]),
+            perf_year: c.number(&["Perf Year"]),
+            perf_ytd: c.number(&["Perf YTD"]),
+            avg_volume: c.number(&["Avg Volume"]),
+            rel_volume: c.number(&["Rel Volume"]),
+            change: c.number(&["Change"]),
+            volume: c.number(&["Volume"]),
+        }))
+    }
+}
+
+impl TableRow for GroupPerformanceRow {
+
+    fn header() -> Vec<String> {
+        ["Name", "Perf Week", "Perf Month", "Perf Quart", "Perf Half", "Perf Year", "Perf YTD", "Avg Volume", "Rel Volume", "Change", "Volume"]
+            .map(String::from).to_vec()
+    }
+
+    fn to_row(&self) -> Vec<String> {
+        let mut row = vec![self.name.to_owned()];
+        row.extend([
+            self.perf_week, self.perf_month, self.perf_quarter, self.perf_half_year, self.perf_year,
+            self.perf_ytd, self.avg_volume, self.rel_volume, self.change, self.volume,
+        ].map(format_number));
+        row
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use strum::IntoEnumIterator;
 
     #[test]
     fn test_url() {
@@ -243,5 +821,104 @@ mod tests {
             assert_eq!(group.get_url(), "https://finviz.com/groups.ashx?g=sector&v=140&o=-name".to_string())
         }
     }
-}
 
+    #[test]
+    fn test_screener_filter() {
+        assert_eq!(GroupBy::Sector.screener_filter("Communication Services"), "sec_communicationservices");
+        assert_eq!(GroupBy::Industry.screener_filter("Oil & Gas E&P"), "ind_oilgasep");
+        assert_eq!(GroupBy::IndustryTechnology.screener_filter("Semiconductors"), "ind_semiconductors");
+        assert_eq!(GroupBy::Country.screener_filter("United Kingdom"), "geo_unitedkingdom");
+        assert_eq!(GroupBy::Capitalization.screener_filter("Mega Cap"), "cap_mega");
+        assert_eq!(GroupBy::Capitalization.screener_filter("Small"), "cap_small");
+    }
+
+    #[test]
+    fn test_chart_url() {
+        let group = Group::default();
+        assert_eq!(group.chart_url(GroupChartTimeFrame::Week), "https://finviz.com/grp_image.ashx?bar_sector_w.png");
+        let group = Group::new(GroupBy::IndustryTechnology, GroupType::Overview, OrderBy::Name, Ordering::Ascending);
+        assert_eq!(group.chart_url(GroupChartTimeFrame::Day), "https://finviz.com/grp_image.ashx?bar_industry_technology_t.png");
+        let group = Group::new(GroupBy::Capitalization, GroupType::Overview, OrderBy::Name, Ordering::Ascending);
+        assert_eq!(group.chart_url(GroupChartTimeFrame::HalfYear), "https://finviz.com/grp_image.ashx?bar_capitalization_h.png");
+    }
+
+    #[test]
+    fn test_constituents() {
+        let group = Group::new(GroupBy::Industry, GroupType::Performance, OrderBy::PerformanceWeek, Ordering::Descending);
+        assert_eq!(group.constituents("Drug Manufacturers - General").to_url(),
+            "https://finviz.com/screener.ashx?v=111&f=ind_drugmanufacturersgeneral");
+    }
+
+    #[test]
+    fn test_url_with_columns() {
+        let mut group = Group::new(GroupBy::Industry, GroupType::Performance, OrderBy::Name, Ordering::Ascending);
+        group.set_columns(&[GroupColumn::Name, GroupColumn::No, GroupColumn::PerformanceWeek, GroupColumn::Name, GroupColumn::NumberOfStocks]);
+        assert_eq!(group.get_url(), "https://finviz.com/groups.ashx?g=industry&v=150&c=0,1,15,26&o=name");
+        assert_eq!(GroupColumn::PerformanceYearToDate.id(), 20);
+        assert_eq!(GroupColumn::PerformanceQuarter.to_string(), "Perf Quart");
+
+        let ids = GroupColumn::iter().map(GroupColumn::id).collect::<std::collections::BTreeSet<_>>();
+        assert_eq!(ids.len(), GroupColumn::iter().count());
+
+        // no column other than No. falls back to the view of the group
+        group.set_columns(&[GroupColumn::No]);
+        assert_eq!(group.get_url(), "https://finviz.com/groups.ashx?g=industry&v=140&o=name");
+        assert_eq!(group.set_columns(&[]).get_url(), "https://finviz.com/groups.ashx?g=industry&v=140&o=name");
+
+        let mut group = Group::new(GroupBy::Sector, GroupType::Valuation, OrderBy::Name, Ordering::Ascending);
+        group.set_columns(&[GroupColumn::Name]).set_columns(&[]);
+        assert_eq!(group.get_url(), "https://finviz.com/groups.ashx?g=sector&v=120&o=name");
+    }
+
+    fn table(rows: &[&[&str]]) -> TableData {
+        rows.iter().map(|row| row.iter().map(|cell| cell.to_string()).collect()).collect()
+    }
+
+    #[test]
+    fn test_overview_rows() {
+        let data = table(&[
+            &["Name", "Stocks", "Market Cap", "Dividend", "P/E", "Fwd P/E", "PEG", "Float Short", "Change", "Volume"],
+            &["Basic Materials", "283", "2,101.84B", "2.45%", "11.97", "15.39", "1.04", "3.20%", "-0.35%", "495.52M"],
+            &["Communication Services", "237", "5,540.17B", "0.82%", "19.49", "-", "1.81", "3.72%", "0.97%", "1.07B"],
+        ]);
+        let rows = GroupOverviewRow::from_table(&data).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].name, "Basic Materials");
+        assert_eq!(rows[0].stocks, Some(283));
+        assert_eq!(rows[0].market_cap, Some(2101.84 * 1e9));
+        assert_eq!(rows[0].dividend_yield, Some(2.45));
+        assert_eq!(rows[0].change, Some(-0.35));
+        assert_eq!(rows[1].forward_pe, None);
+        assert_eq!(rows[1].volume, Some(1.07 * 1e9));
+        assert_eq!(rows[1].to_row()[5], "-");
+    }
+
+    #[test]
+    fn test_performance_rows_by_header_name() {
+        let data = table(&[
+            &["Change", "Name", "Perf YTD", "Perf Week", "Perf Quart"],
+            &["1.10%", "Technology", "35.20%", "2.05%", "10.00%"],
+        ]);
+        let rows = GroupPerformanceRow::from_table(&data).unwrap();
+        assert_eq!(rows[0].name, "Technology");
+        assert_eq!(rows[0].perf_week, Some(2.05));
+        assert_eq!(rows[0].perf_quarter, Some(10.0));
+        assert_eq!(rows[0].perf_ytd, Some(35.2));
+        assert_eq!(rows[0].change, Some(1.1));
+        assert_eq!(rows[0].perf_month, None);
+    }
+
+    #[test]
+    fn test_valuation_rows() {
+        let data = table(&[
+            &["Name", "Market Cap", "P/E", "P/FCF", "EPS next 5Y"],
+            &["Energy", "3,419.18B", "7.97", "9.18", "-4.52%"],
+        ]);
+        let rows = GroupValuationRow::from_table(&data).unwrap();
+        assert_eq!(rows[0].pe, Some(7.97));
+        assert_eq!(rows[0].pfcf, Some(9.18));
+        assert_eq!(rows[0].eps_next_5y, Some(-4.52));
+        assert!(GroupValuationRow::from_table(&table(&[&["Ticker"], &["AAPL"]])).is_err());
+        assert!(GroupValuationRow::from_table(&Vec::new()).is_err());
+    }
+}
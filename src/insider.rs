@@ -1,6 +1,10 @@
 use scraper::{Html, Selector};
 use crate::web_scraper::get_html_body;
-use crate::common::{TableData, Scrape};
+use crate::common::{TableData, TableRow, Scrape};
+use crate::parse::{parse_count, parse_number};
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use std::fmt;
+use std::future::Future;
 use strum::EnumIter;
 use async_trait::async_trait;
 
@@ -19,6 +23,49 @@ pub enum InsiderType {
     Numeric(String),
 }
 
+/// Represents the transaction filter of `Insider::set_transaction`
+#[derive(Clone, Copy, Debug, PartialEq, EnumIter)]
+pub enum TransactionFilter {
+    /// buys and sales
+    All,
+    /// buys only
+    Buys,
+    /// sales only
+    Sales,
+}
+
+impl fmt::Display for TransactionFilter {
+
+    /// Formats the `TransactionFilter` that can be used as URL parameter in `Insider`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TransactionFilter::All => write!(f, "7"),
+            TransactionFilter::Buys => write!(f, "1"),
+            TransactionFilter::Sales => write!(f, "2"),
+        }
+    }
+}
+
+/// Represents the owner relationship filter of `Insider::set_relationship`
+#[derive(Clone, Copy, Debug, PartialEq, EnumIter)]
+pub enum RelationshipFilter {
+    /// owners of more than 10% of the shares only
+    TenPercentOwner,
+    /// officers and directors, excluding owners of more than 10% of the shares
+    OfficerOrDirector,
+}
+
+impl fmt::Display for RelationshipFilter {
+
+    /// Formats the `RelationshipFilter` that can be used as URL parameter in `Insider`
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RelationshipFilter::TenPercentOwner => write!(f, "10"),
+            RelationshipFilter::OfficerOrDirector => write!(f, "-10"),
+        }
+    }
+}
+
 /// This struct represents the insider trading configuration.
 ///
 /// # Example
@@ -42,8 +89,28 @@ pub enum InsiderType {
 /// ```
 ///
 /// The above example demonstrates how to retrive the insider trading data into a table and print it.
+///
+/// The presets of `InsiderType` can be refined by the filters, e.g. the complete history of buys of
+/// a ticker:
+///
+/// ```
+/// use finviz_rs::insider::{Insider, InsiderType, TransactionFilter};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(),Box<dyn std::error::Error>>{
+///     let transactions = Insider::new(InsiderType::Latest)
+///         .set_ticker("AAPL")
+///         .set_transaction(TransactionFilter::Buys)
+///         .set_min_value(100000)
+///         .all_transactions().await?;
+///     println!("{}", transactions.len());
+///     Ok(())
+/// }
+/// ```
 pub struct Insider {
     insider_type: InsiderType,
+    params: Vec<(&'static str, String)>,
+    date_window: Option<(NaiveDate, NaiveDate)>,
 }
 
 impl Default for Insider {
@@ -55,26 +122,189 @@ impl Default for Insider {
 impl Insider {
     const BASE_URL: &'static str = "https://finviz.com/insidertrading.ashx";
 
+    /// The maximum number of pages followed by `scrape_all`.
+    const MAX_PAGES: usize = 100;
+
 
     /// Create a new instance of `Insider` 
     pub fn new(insider_type: InsiderType) -> Self {
-        Self{insider_type}
+        Self{insider_type, params: Vec::new(), date_window: None}
+    }
+
+    /// Sets the transaction filter, overriding the one of the `InsiderType`.
+    pub fn set_transaction(&mut self, transaction: TransactionFilter) -> &mut Self {
+        self.set_param("tc", transaction.to_string())
+    }
+
+    /// Sets the minimum transaction value in dollars, overriding the one of the `InsiderType`.
+    pub fn set_min_value(&mut self, min_value: u64) -> &mut Self {
+        self.set_param("tv", min_value.to_string())
+    }
+
+    /// Sets the owner relationship filter, overriding the one of the `InsiderType`.
+    pub fn set_relationship(&mut self, relationship: RelationshipFilter) -> &mut Self {
+        self.set_param("or", relationship.to_string())
+    }
+
+    /// Only includes the transactions of the ticker.
+    pub fn set_ticker(&mut self, ticker: &str) -> &mut Self {
+        self.set_param("t", ticker.to_uppercase())
+    }
+
+    /// Only includes the transactions of the owner, by the numeric id finviz uses in the owner links.
+    pub fn set_owner(&mut self, owner_id: &str) -> &mut Self {
+        self.set_param("oc", owner_id.to_string())
+    }
+
+    /// Only includes the transactions dated from `from` to `to` (inclusive) in `transactions` and
+    /// `all_transactions`. Finviz has no parameter for it, so the rows are filtered after scraping, and
+    /// `scrape_all` stops following the pages once they are filed before the window.
+    pub fn set_date_window(&mut self, from: NaiveDate, to: NaiveDate) -> &mut Self {
+        self.date_window = Some((from, to));
+        self
+    }
+
+    // helper function to set a URL parameter, replacing the previous value
+    fn set_param(&mut self, key: &'static str, value: String) -> &mut Self {
+        self.params.retain(|(k, _)| *k != key);
+        self.params.push((key, value));
+        self
+    }
+
+    /// Returns the URL parameters of the `InsiderType`.
+    fn preset_params(&self) -> Vec<(&'static str, String)> {
+        let params: &[(&'static str, &str)] = match &self.insider_type {
+            InsiderType::Latest => &[],
+            InsiderType::LatestBuys => &[("tc", "1")],
+            InsiderType::LatestSales => &[("tc", "2")],
+            InsiderType::TopWeek => &[("or", "-10"), ("tv", "100000"), ("tc", "7"), ("o", "-transactionValue")],
+            InsiderType::TopWeekBuys => &[("or", "-10"), ("tv", "100000"), ("tc", "1"), ("o", "-transactionValue")],
+            InsiderType::TopWeekSales => &[("or", "-10"), ("tv", "100000"), ("tc", "2"), ("o", "-transactionValue")],
+            InsiderType::TopOwnerTrade => &[("or", "10"), ("tv", "1000000"), ("tc", "7"), ("o", "-transactionValue")],
+            InsiderType::TopOwnerBuys => &[("or", "10"), ("tv", "1000000"), ("tc", "1"), ("o", "-transactionValue")],
+            InsiderType::TopOwnerSales => &[("or", "10"), ("tv", "1000000"), ("tc", "2"), ("o", "-transactionValue")],
+            InsiderType::Numeric(num) => return vec![("oc", num.to_owned()), ("tc", "7".to_string())],
+        };
+        params.iter().map(|(k, v)| (*k, v.to_string())).collect()
     }
 
     /// Constructs the URL for the insider trading with the specified parameters.
     fn get_url(&self) -> String {
-        match &self.insider_type {
-            InsiderType::Latest => Insider::BASE_URL.to_string(),
-            InsiderType::LatestBuys => format!("{}?tc=1", Insider::BASE_URL),
-            InsiderType::LatestSales => format!("{}?tc=2", Insider::BASE_URL),
-            InsiderType::TopWeek => format!("{}?or=-10&tv=100000&tc=7&o=-transactionValue", Insider::BASE_URL),
-            InsiderType::TopWeekBuys => format!("{}?or=-10&tv=100000&tc=1&o=-transactionValue", Insider::BASE_URL),
-            InsiderType::TopWeekSales => format!("{}?or=-10&tv=100000&tc=2&o=-transactionValue", Insider::BASE_URL),
-            InsiderType::TopOwnerTrade => format!("{}?or=10&tv=1000000&tc=7&o=-transactionValue", Insider::BASE_URL),
-            InsiderType::TopOwnerBuys => format!("{}?or=10&tv=1000000&tc=1&o=-transactionValue", Insider::BASE_URL),
-            InsiderType::TopOwnerSales => format!("{}?or=10&tv=1000000&tc=2&o=-transactionValue", Insider::BASE_URL),
-            InsiderType::Numeric(num) => format!("{}?oc={}&tc=7", Insider::BASE_URL, num),
+        self.get_page_url(None)
+    }
+
+    /// Constructs the URL of the page starting at the given row (1-based) of the results.
+    fn get_page_url(&self, start_row: Option<usize>) -> String {
+        let mut params = self.preset_params();
+        for (key, value) in self.params.iter() {
+            match params.iter_mut().find(|(k, _)| k == key) {
+                Some(param) => param.1 = value.to_owned(),
+                None => params.push((key, value.to_owned())),
+            }
+        }
+        if let Some(start_row) = start_row {
+            params.push(("r", start_row.to_string()));
+        }
+
+        if params.is_empty() {
+            return Insider::BASE_URL.to_string();
         }
+        let query = params.iter().map(|(k, v)| format!("{}={}", k, v)).collect::<Vec<_>>().join("&");
+        format!("{}?{}", Insider::BASE_URL, query)
+    }
+
+    /// Scrapes all pages of the insider trading data by following the pagination, and return the
+    /// `TableData` of all rows on success.
+    ///
+    /// The pagination stops at the first page with fewer rows than the first page, or when finviz
+    /// repeats the previous page. With `set_date_window` and the default order by filing time, it also
+    /// stops at the first page filed before the window. At most 100 pages are followed, and an error is
+    /// returned if there are more, instead of truncating the result silently.
+    pub async fn scrape_all(&self) -> Result<TableData, Box<dyn std::error::Error>> {
+        self.scrape_pages(|url| async move { get_html_body(&url).await }).await
+    }
+
+    // helper function of `scrape_all` with the function fetching a page by URL
+    async fn scrape_pages<F, Fut>(&self, mut fetch: F) -> Result<TableData, Box<dyn std::error::Error>>
+    where
+        F: FnMut(String) -> Fut,
+        Fut: Future<Output = Result<String, Box<dyn std::error::Error>>>,
+    {
+        let today = finviz_today();
+        // the rows are ordered by filing time unless another order is set, and a transaction is
+        // never filed before its date, so a page filed before the window ends the rows in it
+        let window_start = self.date_window
+            .map(|(from, _)| from)
+            .filter(|_| !self.preset_params().iter().chain(self.params.iter()).any(|(key, _)| *key == "o"));
+
+        let mut frame: TableData = Vec::new();
+        let mut page_size = 0;
+        let mut previous_first_row = None;
+
+        for page_index in 0..Insider::MAX_PAGES {
+            let start_row = if page_index == 0 { None } else { Some(page_index * page_size + 1) };
+            let page = parse_insider_table(&fetch(self.get_page_url(start_row)).await?)?;
+
+            // finviz returns the last page again when the start row is out of range
+            if page.is_empty() || page.first() == previous_first_row.as_ref() {
+                return Ok(frame);
+            }
+            if page_index == 0 {
+                page_size = page.len();
+            }
+
+            let is_last_page = page.len() < page_size;
+            let is_before_window = window_start.is_some_and(|from| {
+                page.iter()
+                    .filter_map(|row| parse_insider_time(row.get(9)?, today))
+                    .min()
+                    .is_some_and(|oldest| oldest.date() < from)
+            });
+            previous_first_row = page.first().cloned();
+            frame.extend(page);
+
+            if is_last_page || is_before_window {
+                return Ok(frame);
+            }
+        }
+
+        Err(format!("Insider trading has more than {} pages, narrow down the filters", Insider::MAX_PAGES).into())
+    }
+
+    /// Scrapes the insider trading data like `scrape`, and converts each row to `InsiderTransaction`.
+    pub async fn transactions(&self) -> Result<Vec<InsiderTransaction>, Box<dyn std::error::Error>> {
+        self.to_transactions(self.scrape().await?)
+    }
+
+    /// Scrapes all pages of the insider trading data like `scrape_all`, and converts each row to `InsiderTransaction`.
+    pub async fn all_transactions(&self) -> Result<Vec<InsiderTransaction>, Box<dyn std::error::Error>> {
+        self.to_transactions(self.scrape_all().await?)
+    }
+
+    // helper function to convert the rows to `InsiderTransaction` within the date window
+    fn to_transactions(&self, frame: TableData) -> Result<Vec<InsiderTransaction>, Box<dyn std::error::Error>> {
+        let (transactions, mut errors) = self.parse_transactions(&frame);
+        match errors.is_empty() {
+            true => Ok(transactions),
+            false => Err(errors.remove(0)),
+        }
+    }
+
+    /// Converts the rows returned by `scrape` or `scrape_all` to `InsiderTransaction` within the date
+    /// window, and returns the errors of the rows that cannot be converted separately, so a malformed
+    /// row does not fail the other rows.
+    pub fn parse_transactions(&self, frame: &TableData) -> (Vec<InsiderTransaction>, Vec<Box<dyn std::error::Error>>) {
+        let today = finviz_today();
+        let mut transactions = Vec::new();
+        let mut errors = Vec::new();
+        for row in frame.iter() {
+            match InsiderTransaction::parse_row(row, today) {
+                Ok(t) if self.date_window.is_none_or(|(from, to)| t.date >= from && t.date <= to) => transactions.push(t),
+                Ok(_) => {},
+                Err(e) => errors.push(e),
+            }
+        }
+        (transactions, errors)
     }
 
     /// Returns the default header for insider trading data.
@@ -93,47 +323,286 @@ impl Scrape<TableData> for Insider {
     async fn scrape(&self) -> Result<TableData, Box<dyn std::error::Error>> {
         let url = self.get_url();
         let body = get_html_body(&url).await?;
-        let document = Html::parse_document(&body);
+        parse_insider_table(&body)
+    }
+
+}
 
-        let table_selector = Selector::parse("table.styled-table-new")?;
-        let row_selector = Selector::parse("tr")?;
-        let header_selector = Selector::parse("td")?;
-        let link_selector = Selector::parse("a")?;
+// helper function to parse the insider trading table, appending the link of the SEC Form 4 to each row
+fn parse_insider_table(body: &str) -> Result<TableData, Box<dyn std::error::Error>> {
+    let document = Html::parse_document(body);
 
-        let insider_trader = document.select(&table_selector).next().ok_or("Table not found")?;
-        let rows = insider_trader.select(&row_selector).collect::<Vec<_>>();
+    let table_selector = Selector::parse("table.styled-table-new")?;
+    let row_selector = Selector::parse("tr")?;
+    let header_selector = Selector::parse("td")?;
+    let link_selector = Selector::parse("a")?;
 
-        let mut frame = Vec::new();
+    let insider_trader = document.select(&table_selector).next().ok_or("Table not found")?;
 
-        // Skip the first row since the default header is used
-        for row in rows[1..].iter() {
-            let cols = row.select(&header_selector).collect::<Vec<_>>();
-            if cols.len() < 5 {
-                continue;
-            }
+    let mut frame = Vec::new();
 
-            let mut info_dict = Vec::new();
-            for (i, col) in cols.iter().enumerate() {
-                //match i 
-                match i {
-                    n if i == cols.len() - 1 => {
-                        let link = cols[n].select(&link_selector).next();
-                        info_dict.push(col.text().collect::<String>());
-                        if let Some(a) = link {
-                            info_dict.push(a.value().attr("href").unwrap_or("").to_string());
-                        } else {
-                            info_dict.push(String::new());
-                        }
-                    },
-                    _ => info_dict.push(col.text().collect::<String>())
-                };
-            }
-            frame.push(info_dict);
+    // Skip the first row since the default header is used
+    for row in insider_trader.select(&row_selector).skip(1) {
+        let cols = row.select(&header_selector).collect::<Vec<_>>();
+        if cols.len() < 5 {
+            continue;
+        }
+
+        let mut info_dict = Vec::new();
+        for (i, col) in cols.iter().enumerate() {
+            //match i 
+            match i {
+                n if i == cols.len() - 1 => {
+                    let link = cols[n].select(&link_selector).next();
+                    info_dict.push(col.text().collect::<String>());
+                    if let Some(a) = link {
+                        info_dict.push(absolute_url(a.value().attr("href").unwrap_or("")));
+                    } else {
+                        info_dict.push(String::new());
+                    }
+                },
+                _ => info_dict.push(col.text().collect::<String>())
+            };
         }
+        frame.push(info_dict);
+    }
+
+    Ok(frame)
+}
 
-        Ok(frame)
+// helper function to resolve a link of the insider trading page to an absolute URL
+fn absolute_url(href: &str) -> String {
+    if href.is_empty() || href.starts_with("http://") || href.starts_with("https://") {
+        href.to_string()
+    } else if let Some(href) = href.strip_prefix("//") {
+        format!("https://{}", href)
+    } else {
+        format!("https://finviz.com/{}", href.trim_start_matches('/'))
     }
+}
 
+/// Represents the relationship of an insider to the company
+#[derive(Clone, Debug, PartialEq)]
+pub enum Relationship {
+    /// chief executive officer
+    ChiefExecutiveOfficer,
+    /// chief financial officer
+    ChiefFinancialOfficer,
+    /// chief operating officer
+    ChiefOperatingOfficer,
+    /// president
+    President,
+    /// director
+    Director,
+    /// owner of more than 10% of the shares
+    TenPercentOwner,
+    /// any other officer, e.g. a vice president or the general counsel
+    Officer(String),
+    /// a relationship not recognized, as shown on finviz
+    Other(String),
+}
+
+impl From<&str> for Relationship {
+
+    /// Classifies the relationship shown on finviz, e.g. "Chief Executive Officer" or "CEO"
+    fn from(text: &str) -> Self {
+        let text = text.trim();
+        let upper = text.to_uppercase();
+        // whole words only, so "COO" does not match "Coordinator"
+        let tokens = upper.split(|c: char| !(c.is_alphanumeric() || c == '%'))
+            .filter(|token| !token.is_empty())
+            .collect::<Vec<_>>();
+        let has = |phrases: &[&str]| phrases.iter().any(|phrase| {
+            let words = phrase.split(' ').collect::<Vec<_>>();
+            tokens.windows(words.len()).any(|window| window == words.as_slice())
+        });
+
+        if has(&["10%"]) {
+            Relationship::TenPercentOwner
+        } else if has(&["CEO", "CHIEF EXECUTIVE"]) {
+            Relationship::ChiefExecutiveOfficer
+        } else if has(&["CFO", "CHIEF FINANCIAL"]) {
+            Relationship::ChiefFinancialOfficer
+        } else if has(&["COO", "CHIEF OPERATING"]) {
+            Relationship::ChiefOperatingOfficer
+        } else if has(&["PRESIDENT"]) && !has(&["VICE", "VP", "EVP", "SVP"]) {
+            Relationship::President
+        } else if has(&["DIRECTOR"]) && !has(&["OFFICER"]) {
+            Relationship::Director
+        } else if has(&["OFFICER", "CHIEF", "VP", "EVP", "SVP", "VICE", "COUNSEL", "SECRETARY", "TREASURER", "CONTROLLER"]) {
+            Relationship::Officer(text.to_string())
+        } else {
+            Relationship::Other(text.to_string())
+        }
+    }
+}
+
+impl fmt::Display for Relationship {
+
+    /// Formats the `Relationship` as a readable string
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Relationship::ChiefExecutiveOfficer => write!(f, "Chief Executive Officer"),
+            Relationship::ChiefFinancialOfficer => write!(f, "Chief Financial Officer"),
+            Relationship::ChiefOperatingOfficer => write!(f, "Chief Operating Officer"),
+            Relationship::President => write!(f, "President"),
+            Relationship::Director => write!(f, "Director"),
+            Relationship::TenPercentOwner => write!(f, "10% Owner"),
+            Relationship::Officer(text) | Relationship::Other(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+/// Represents the kind of an insider transaction
+#[derive(Clone, Debug, PartialEq)]
+pub enum TransactionKind {
+    /// open market purchase
+    Buy,
+    /// open market sale
+    Sale,
+    /// exercise of options
+    OptionExercise,
+    /// sale proposed by Form 144
+    ProposedSale,
+    /// a transaction not recognized, as shown on finviz
+    Other(String),
+}
+
+impl From<&str> for TransactionKind {
+
+    /// Classifies the transaction shown on finviz, e.g. "Buy" or "Option Exercise"
+    fn from(text: &str) -> Self {
+        match text.trim() {
+            "Buy" => TransactionKind::Buy,
+            "Sale" => TransactionKind::Sale,
+            "Option Exercise" => TransactionKind::OptionExercise,
+            "Proposed Sale" => TransactionKind::ProposedSale,
+            text => TransactionKind::Other(text.to_string()),
+        }
+    }
+}
+
+impl fmt::Display for TransactionKind {
+
+    /// Formats the `TransactionKind` as shown on finviz
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TransactionKind::Buy => write!(f, "Buy"),
+            TransactionKind::Sale => write!(f, "Sale"),
+            TransactionKind::OptionExercise => write!(f, "Option Exercise"),
+            TransactionKind::ProposedSale => write!(f, "Proposed Sale"),
+            TransactionKind::Other(text) => write!(f, "{}", text),
+        }
+    }
+}
+
+/// A typed row of the insider trading data, returned by `Insider::transactions`
+#[derive(Clone, Debug, PartialEq)]
+pub struct InsiderTransaction {
+    /// ticker of the company
+    pub ticker: String,
+    /// name of the insider
+    pub owner: String,
+    /// relationship of the insider to the company
+    pub relationship: Relationship,
+    /// date of the transaction
+    pub date: NaiveDate,
+    /// kind of the transaction
+    pub transaction: TransactionKind,
+    /// price per share
+    pub cost: Option<f64>,
+    /// number of shares traded
+    pub shares: Option<u64>,
+    /// value of the transaction in dollars
+    pub value: Option<u64>,
+    /// number of shares owned after the transaction
+    pub shares_total: Option<u64>,
+    /// time when the SEC Form 4 was filed, in the time zone shown on finviz
+    pub filed: NaiveDateTime,
+    /// absolute URL of the SEC Form 4
+    pub form4_url: String,
+}
+
+impl InsiderTransaction {
+
+    /// Converts a row returned by `Insider::scrape` into `InsiderTransaction`. Finviz omits the year of
+    /// recent dates, which is resolved as the latest year that does not put the date after today in New York.
+    pub fn from_row(row: &[String]) -> Result<Self, Box<dyn std::error::Error>> {
+        InsiderTransaction::parse_row(row, finviz_today())
+    }
+
+    // helper function of `from_row` relative to the given date of today
+    fn parse_row(row: &[String], today: NaiveDate) -> Result<Self, Box<dyn std::error::Error>> {
+        let [ticker, owner, relationship, date, transaction, cost, shares, value, shares_total, filed, form4_url] = row else {
+            return Err(format!("Expect {} columns of insider trading, but got {}", Insider::default_header().len(), row.len()).into());
+        };
+
+        Ok(InsiderTransaction {
+            ticker: ticker.trim().to_string(),
+            owner: owner.trim().to_string(),
+            relationship: Relationship::from(relationship.as_str()),
+            date: parse_insider_date(date, today).ok_or(format!("Invalid date of insider trading: {}", date))?,
+            transaction: TransactionKind::from(transaction.as_str()),
+            cost: parse_number(cost),
+            shares: parse_count(shares),
+            value: parse_count(value),
+            shares_total: parse_count(shares_total),
+            filed: parse_insider_time(filed, today).ok_or(format!("Invalid filing time of insider trading: {}", filed))?,
+            form4_url: absolute_url(form4_url.trim()),
+        })
+    }
+}
+
+impl TableRow for InsiderTransaction {
+
+    fn header() -> Vec<String> {
+        Insider::default_header()
+    }
+
+    fn to_row(&self) -> Vec<String> {
+        let optional = |value: Option<u64>| value.map_or("-".to_string(), |v| v.to_string());
+        vec![
+            self.ticker.to_owned(),
+            self.owner.to_owned(),
+            self.relationship.to_string(),
+            self.date.to_string(),
+            self.transaction.to_string(),
+            self.cost.map_or("-".to_string(), |v| v.to_string()),
+            optional(self.shares),
+            optional(self.value),
+            optional(self.shares_total),
+            self.filed.format("%Y-%m-%d %H:%M").to_string(),
+            self.form4_url.to_owned(),
+        ]
+    }
+}
+
+// helper function to parse a date such as "May 12" or "May 12 '23"
+fn parse_insider_date(text: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let text = text.trim();
+    if let Ok(date) = NaiveDate::parse_from_str(text, "%b %d '%y") {
+        return Some(date);
+    }
+
+    // parsed in a leap year, so "Feb 29" is accepted and resolved as the latest leap year
+    let date = NaiveDate::parse_from_str(&format!("{} 2000", text), "%b %d %Y").ok()?;
+    (today.year() - 8..=today.year()).rev()
+        .filter_map(|year| date.with_year(year))
+        .find(|date| *date <= today)
+}
+
+// helper function of today in the time zone of finviz, which resolves the dates without a year
+fn finviz_today() -> NaiveDate {
+    Utc::now().with_timezone(&chrono_tz::America::New_York).date_naive()
+}
+
+// helper function to parse a filing time such as "May 15 09:35 PM" or "May 15 '23 09:35 PM"
+fn parse_insider_time(text: &str, today: NaiveDate) -> Option<NaiveDateTime> {
+    let text = text.trim();
+    let (date, time) = text.rsplit_once(' ')
+        .and_then(|(rest, meridiem)| rest.rsplit_once(' ').map(|(date, time)| (date, format!("{} {}", time, meridiem))))?;
+    let time = NaiveTime::parse_from_str(&time, "%I:%M %p").ok()?;
+    Some(parse_insider_date(date, today)?.and_time(time))
 }
 
 #[cfg(test)]
@@ -147,10 +616,181 @@ mod tests {
         assert!(result.is_ok(), "failed to get insider info");
     }
 
+    #[test]
+    fn test_parse_transaction() {
+        let today = NaiveDate::from_ymd_opt(2023, 5, 16).unwrap();
+        let row = ["SKLZ", "Paradise Andrew", "Chief Executive Officer", "May 12", "Buy", "0.52", "1,342,656", "698,181", "10,299,303",
+                   "May 15 09:35 PM", "http://www.sec.gov/Archives/edgar/data/1801661/000180166123000041/xslF345X04/wf-form4_168419972013617.xml"]
+            .map(String::from);
+        let transaction = InsiderTransaction::parse_row(&row, today).unwrap();
+        assert_eq!(transaction.relationship, Relationship::ChiefExecutiveOfficer);
+        assert_eq!(transaction.date, NaiveDate::from_ymd_opt(2023, 5, 12).unwrap());
+        assert_eq!(transaction.transaction, TransactionKind::Buy);
+        assert_eq!(transaction.cost, Some(0.52));
+        assert_eq!(transaction.shares, Some(1_342_656));
+        assert_eq!(transaction.value, Some(698_181));
+        assert_eq!(transaction.filed.to_string(), "2023-05-15 21:35:00");
+        assert!(transaction.form4_url.starts_with("http://www.sec.gov/"));
+
+        // a date after today belongs to the previous year
+        let mut row = row;
+        row[3] = "Dec 28".to_string();
+        row[9] = "Dec 29 08:05 AM".to_string();
+        let transaction = InsiderTransaction::parse_row(&row, today).unwrap();
+        assert_eq!(transaction.date, NaiveDate::from_ymd_opt(2022, 12, 28).unwrap());
+        assert_eq!(transaction.filed.to_string(), "2022-12-29 08:05:00");
+
+        assert!(InsiderTransaction::parse_row(&row[..5], today).is_err());
+    }
+
+    #[test]
+    fn test_parse_leap_day() {
+        let today = NaiveDate::from_ymd_opt(2023, 5, 16).unwrap();
+        assert_eq!(parse_insider_date("Feb 29", today), NaiveDate::from_ymd_opt(2020, 2, 29));
+        let today = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+        assert_eq!(parse_insider_date("Feb 29", today), NaiveDate::from_ymd_opt(2024, 2, 29));
+        let today = NaiveDate::from_ymd_opt(2024, 2, 28).unwrap();
+        assert_eq!(parse_insider_date("Feb 29", today), NaiveDate::from_ymd_opt(2020, 2, 29));
+        assert_eq!(parse_insider_date("Feb 30", today), None);
+    }
+
+    #[test]
+    fn test_relationship_and_url() {
+        assert_eq!(Relationship::from("10% Owner"), Relationship::TenPercentOwner);
+        assert_eq!(Relationship::from("Director"), Relationship::Director);
+        assert_eq!(Relationship::from("EVP, General Counsel"), Relationship::Officer("EVP, General Counsel".to_string()));
+        assert_eq!(Relationship::from("Former Employee"), Relationship::Other("Former Employee".to_string()));
+        assert_eq!(Relationship::from("President and CEO"), Relationship::ChiefExecutiveOfficer);
+        assert_eq!(Relationship::from("SVP, Controller"), Relationship::Officer("SVP, Controller".to_string()));
+        assert_eq!(Relationship::from("Coordinator"), Relationship::Other("Coordinator".to_string()));
+        assert_eq!(Relationship::from("Sr. Vice President"), Relationship::Officer("Sr. Vice President".to_string()));
+        assert_eq!(absolute_url("/insidertrading.ashx?oc=1"), "https://finviz.com/insidertrading.ashx?oc=1");
+        assert_eq!(absolute_url("//www.sec.gov/Archives/a.xml"), "https://www.sec.gov/Archives/a.xml");
+    }
+
+    // helper function of an insider trading page with a row per (ticker, date, filing time)
+    fn insider_page(rows: &[(&str, &str, &str)]) -> String {
+        let rows = rows.iter()
+            .map(|(ticker, date, filed)| format!(
+                "<tr><td>{}</td><td>Doe John</td><td>Director</td><td>{}</td><td>Buy</td><td>10.5</td>\
+                 <td>1,000</td><td>10,500</td><td>5,000</td><td><a href=\"//www.sec.gov/{}.xml\">{}</a></td></tr>",
+                ticker, date, ticker, filed))
+            .collect::<String>();
+        format!("<html><table class=\"styled-table-new\"><tr><td>Ticker</td></tr>{}</table></html>", rows)
+    }
+
+    // helper function to scrape the pages of an insider by their start row, and return the start rows requested
+    async fn scrape_fixture(insider: &Insider, pages: &[String]) -> (Result<TableData, Box<dyn std::error::Error>>, Vec<usize>) {
+        let mut requested = Vec::new();
+        let result = insider.scrape_pages(|url| {
+            let start_row = url.split(['?', '&']).find_map(|param| param.strip_prefix("r=")).map_or(1, |r| r.parse().unwrap());
+            requested.push(start_row);
+            let page = pages.get((start_row - 1) / 3).or(pages.last()).unwrap().to_owned();
+            async move { Ok(page) }
+        }).await;
+        (result, requested)
+    }
+
+    #[test]
+    fn test_parse_insider_table() {
+        let body = insider_page(&[("AAPL", "May 12 '23", "May 15 '23 09:35 PM"), ("MSFT", "May 11 '23", "May 15 '23 08:00 AM")]);
+        let table = parse_insider_table(&body).unwrap();
+        assert_eq!(table.len(), 2);
+        assert_eq!(table[0].len(), Insider::default_header().len());
+        assert_eq!(table[0][0], "AAPL");
+        assert_eq!(table[1][9], "May 15 '23 08:00 AM");
+        assert_eq!(table[1][10], "https://www.sec.gov/MSFT.xml");
+        assert!(parse_insider_table("<html></html>").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_scrape_pages() {
+        // identical filings are kept, and the last page is shorter than the page size
+        let pages = [
+            insider_page(&[("AAA", "May 12 '23", "May 15 '23 09:35 PM"), ("AAA", "May 12 '23", "May 15 '23 09:35 PM"), ("BBB", "May 11 '23", "May 15 '23 08:00 AM")]),
+            insider_page(&[("CCC", "May 10 '23", "May 14 '23 08:00 AM"), ("DDD", "May 10 '23", "May 13 '23 08:00 AM"), ("EEE", "May 9 '23", "May 12 '23 08:00 AM")]),
+            insider_page(&[("FFF", "May 8 '23", "May 11 '23 08:00 AM")]),
+        ];
+        let (result, requested) = scrape_fixture(&Insider::default(), &pages).await;
+        let table = result.unwrap();
+        assert_eq!(table.iter().map(|row| row[0].as_str()).collect::<Vec<_>>(), vec!["AAA", "AAA", "BBB", "CCC", "DDD", "EEE", "FFF"]);
+        assert_eq!(requested, vec![1, 4, 7]);
+
+        // finviz repeats the last page when the start row is out of range
+        let (result, requested) = scrape_fixture(&Insider::default(), &pages[..2]).await;
+        assert_eq!(result.unwrap().len(), 6);
+        assert_eq!(requested, vec![1, 4, 7]);
+
+        // more pages than the limit is an error rather than a truncated result
+        let mut insider = Insider::default();
+        let mut requested = 0;
+        let result = insider.set_ticker("AAA").scrape_pages(|_| {
+            requested += 1;
+            let page = insider_page(&[(&format!("T{}", requested), "May 12 '23", "May 15 '23 09:35 PM")]);
+            async move { Ok(page) }
+        }).await;
+        assert!(result.is_err());
+        assert_eq!(requested, Insider::MAX_PAGES);
+    }
+
+    #[test]
+    fn test_parse_transactions() {
+        let body = insider_page(&[("AAA", "May 12 '23", "May 15 '23 09:35 PM"), ("BBB", "Someday", "May 15 '23 08:00 AM")]);
+        let table = parse_insider_table(&body).unwrap();
+        let insider = Insider::default();
+        let (transactions, errors) = insider.parse_transactions(&table);
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].ticker, "AAA");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].to_string(), "Invalid date of insider trading: Someday");
+        assert!(insider.to_transactions(table).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_scrape_pages_in_date_window() {
+        let pages = [
+            insider_page(&[("AAA", "May 12 '23", "May 15 '23 09:35 PM"), ("BBB", "May 1 '23", "May 15 '23 08:00 AM"), ("CCC", "May 11 '23", "May 14 '23 08:00 AM")]),
+            insider_page(&[("DDD", "May 10 '23", "May 13 '23 08:00 AM"), ("EEE", "May 9 '23", "May 11 '23 08:00 AM"), ("FFF", "May 8 '23", "May 9 '23 08:00 AM")]),
+            insider_page(&[("GGG", "May 7 '23", "May 8 '23 08:00 AM"), ("HHH", "May 6 '23", "May 7 '23 08:00 AM"), ("III", "May 5 '23", "May 6 '23 08:00 AM")]),
+        ];
+        let mut insider = Insider::default();
+        insider.set_date_window(NaiveDate::from_ymd_opt(2023, 5, 10).unwrap(), NaiveDate::from_ymd_opt(2023, 5, 12).unwrap());
+
+        // a late filing of an old transaction does not stop the pagination, a page filed before the window does
+        let (result, requested) = scrape_fixture(&insider, &pages).await;
+        let table = result.unwrap();
+        assert_eq!(requested, vec![1, 4]);
+        assert_eq!(table.len(), 6);
+
+        let transactions = insider.to_transactions(table).unwrap();
+        assert_eq!(transactions.iter().map(|t| t.ticker.as_str()).collect::<Vec<_>>(), vec!["AAA", "CCC", "DDD"]);
+
+        // the pagination is not stopped by the date when ordered by the transaction value
+        let mut insider = Insider::new(InsiderType::TopWeek);
+        insider.set_date_window(NaiveDate::from_ymd_opt(2023, 5, 10).unwrap(), NaiveDate::from_ymd_opt(2023, 5, 12).unwrap());
+        let (_, requested) = scrape_fixture(&insider, &pages).await;
+        assert_eq!(requested, vec![1, 4, 7, 10]);
+    }
+
     #[test]
     fn test_url() {
         let insider = Insider::new(InsiderType::TopWeek);
         assert_eq!(insider.get_url(), "https://finviz.com/insidertrading.ashx?or=-10&tv=100000&tc=7&o=-transactionValue".to_string())
     }
+
+    #[test]
+    fn test_url_with_filters() {
+        let mut insider = Insider::new(InsiderType::TopWeek);
+        insider.set_transaction(TransactionFilter::Sales)
+            .set_min_value(500000)
+            .set_relationship(RelationshipFilter::TenPercentOwner)
+            .set_ticker("aapl");
+        assert_eq!(insider.get_url(), "https://finviz.com/insidertrading.ashx?or=10&tv=500000&tc=2&o=-transactionValue&t=AAPL");
+
+        let mut insider = Insider::default();
+        insider.set_owner("1214128").set_transaction(TransactionFilter::All);
+        assert_eq!(insider.get_url(), "https://finviz.com/insidertrading.ashx?oc=1214128&tc=7");
+        assert_eq!(insider.get_page_url(Some(101)), "https://finviz.com/insidertrading.ashx?oc=1214128&tc=7&r=101");
+    }
 }
 
@@ -0,0 +1,301 @@
+use crate::insider::{InsiderTransaction, TransactionKind};
+use crate::common::TableRow;
+use chrono::{Duration, NaiveDate};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Net buying and selling of a ticker or an owner, returned by `InsiderAnalysis`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct InsiderSummary {
+    /// the ticker or the owner the transactions are aggregated by
+    pub key: String,
+    /// value of all buys in dollars
+    pub buy_value: u64,
+    /// value of all sales in dollars
+    pub sale_value: u64,
+    /// buy value minus sale value
+    pub net_value: i64,
+    /// number of shares bought
+    pub buy_shares: u64,
+    /// number of shares sold
+    pub sale_shares: u64,
+    /// shares bought minus shares sold
+    pub net_shares: i64,
+    /// number of buys and sales
+    pub transactions: usize,
+}
+
+impl TableRow for InsiderSummary {
+
+    fn header() -> Vec<String> {
+        ["Key", "Buy Value", "Sale Value", "Net Value", "Buy Shares", "Sale Shares", "Net Shares", "Transactions"]
+            .map(String::from).to_vec()
+    }
+
+    fn to_row(&self) -> Vec<String> {
+        vec![
+            self.key.to_owned(),
+            self.buy_value.to_string(),
+            self.sale_value.to_string(),
+            self.net_value.to_string(),
+            self.buy_shares.to_string(),
+            self.sale_shares.to_string(),
+            self.net_shares.to_string(),
+            self.transactions.to_string(),
+        ]
+    }
+}
+
+/// Buys of the same ticker by several insiders within a short period, returned by `InsiderAnalysis::clusters`
+#[derive(Clone, Debug, PartialEq)]
+pub struct InsiderCluster {
+    /// ticker of the company
+    pub ticker: String,
+    /// distinct insiders who bought, in alphabetical order
+    pub owners: Vec<String>,
+    /// date of the first buy of the cluster
+    pub start: NaiveDate,
+    /// date of the last buy of the cluster
+    pub end: NaiveDate,
+    /// value of the buys in dollars
+    pub value: u64,
+    /// number of shares bought
+    pub shares: u64,
+}
+
+impl TableRow for InsiderCluster {
+
+    fn header() -> Vec<String> {
+        ["Ticker", "Insiders", "Owners", "Start", "End", "Value", "Shares"].map(String::from).to_vec()
+    }
+
+    fn to_row(&self) -> Vec<String> {
+        vec![
+            self.ticker.to_owned(),
+            self.owners.len().to_string(),
+            self.owners.join(", "),
+            self.start.to_string(),
+            self.end.to_string(),
+            self.value.to_string(),
+            self.shares.to_string(),
+        ]
+    }
+}
+
+/// This struct analyses the results of `Insider::transactions`, aggregating net buying and selling
+/// and detecting cluster buys. Only buys and sales are counted, option exercises and proposed sales
+/// are ignored.
+///
+/// # Example
+///
+/// ```
+/// use finviz_rs::{
+///     insider::{Insider, InsiderType},
+///     insider_analysis::InsiderAnalysis,
+///     output::ToTable,
+/// };
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(),Box<dyn std::error::Error>>{
+///     let transactions = Insider::new(InsiderType::LatestBuys).transactions().await?;
+///     let mut analysis = InsiderAnalysis::new(&transactions);
+///     analysis.set_window(30);
+///     println!("{}", analysis.by_ticker().to_table(None, Some(5)));
+///     println!("{}", analysis.clusters(3, 7).to_table(None, Some(5)));
+///     Ok(())
+/// }
+/// ```
+///
+/// The above example demonstrates how to rank the tickers by net buying over 30 days, and find 3 insiders buying within 7 days.
+pub struct InsiderAnalysis<'a> {
+    transactions: &'a [InsiderTransaction],
+    window: Option<i64>,
+    as_of: Option<NaiveDate>,
+}
+
+impl<'a> InsiderAnalysis<'a> {
+
+    /// Creates a new `InsiderAnalysis` over all the transactions.
+    pub fn new(transactions: &'a [InsiderTransaction]) -> Self {
+        Self { transactions, window: None, as_of: None }
+    }
+
+    /// Only includes the transactions of the last `days` days up to the `as_of` date.
+    pub fn set_window(&mut self, days: i64) -> &mut Self {
+        self.window = Some(days);
+        self
+    }
+
+    /// Sets the end date of the window, which is the date of the latest transaction by default.
+    pub fn set_as_of(&mut self, as_of: NaiveDate) -> &mut Self {
+        self.as_of = Some(as_of);
+        self
+    }
+
+    /// Aggregates the transactions per ticker, ranked by net value from the largest buying.
+    pub fn by_ticker(&self) -> Vec<InsiderSummary> {
+        self.aggregate(|t| &t.ticker)
+    }
+
+    /// Aggregates the transactions per owner, ranked by net value from the largest buying.
+    pub fn by_owner(&self) -> Vec<InsiderSummary> {
+        self.aggregate(|t| &t.owner)
+    }
+
+    /// Finds the tickers bought by at least `min_insiders` distinct insiders within `days` days, and
+    /// returns the cluster with the most insiders of each ticker, ranked by number of insiders and value.
+    pub fn clusters(&self, min_insiders: usize, days: i64) -> Vec<InsiderCluster> {
+        let mut buys: BTreeMap<&str, Vec<&InsiderTransaction>> = BTreeMap::new();
+        for transaction in self.in_window().filter(|t| t.transaction == TransactionKind::Buy) {
+            buys.entry(&transaction.ticker).or_default().push(transaction);
+        }
+
+        let mut clusters = Vec::new();
+        for (ticker, mut transactions) in buys {
+            transactions.sort_by_key(|t| t.date);
+
+            let best = transactions.iter()
+                .map(|first| {
+                    let members = transactions.iter()
+                        .filter(|t| t.date >= first.date && t.date <= first.date + Duration::days(days))
+                        .collect::<Vec<_>>();
+                    InsiderCluster {
+                        ticker: ticker.to_string(),
+                        owners: members.iter().map(|t| t.owner.to_owned()).collect::<BTreeSet<_>>().into_iter().collect(),
+                        start: first.date,
+                        end: members.iter().map(|t| t.date).max().unwrap_or(first.date),
+                        value: members.iter().filter_map(|t| t.value).sum(),
+                        shares: members.iter().filter_map(|t| t.shares).sum(),
+                    }
+                })
+                .max_by(|a, b| a.owners.len().cmp(&b.owners.len()).then(a.value.cmp(&b.value)));
+
+            if let Some(cluster) = best.filter(|c| c.owners.len() >= min_insiders) {
+                clusters.push(cluster);
+            }
+        }
+
+        clusters.sort_by(|a, b| b.owners.len().cmp(&a.owners.len()).then(b.value.cmp(&a.value)));
+        clusters
+    }
+
+    // helper function to iterate the transactions within the window
+    fn in_window(&self) -> impl Iterator<Item = &'a InsiderTransaction> {
+        let as_of = self.as_of.or_else(|| self.transactions.iter().map(|t| t.date).max());
+        let start = match (self.window, as_of) {
+            (Some(days), Some(as_of)) => Some(as_of - Duration::days(days)),
+            _ => None,
+        };
+
+        self.transactions.iter()
+            .filter(move |t| as_of.is_none_or(|as_of| t.date <= as_of))
+            .filter(move |t| start.is_none_or(|start| t.date > start))
+    }
+
+    // helper function to aggregate the transactions in the window by the given key
+    fn aggregate<F>(&self, key: F) -> Vec<InsiderSummary>
+    where
+        F: Fn(&InsiderTransaction) -> &String,
+    {
+        let mut summaries: BTreeMap<&String, InsiderSummary> = BTreeMap::new();
+        for transaction in self.in_window() {
+            let (value, shares) = (transaction.value.unwrap_or_default(), transaction.shares.unwrap_or_default());
+            let summary = summaries.entry(key(transaction)).or_insert_with(|| InsiderSummary {
+                key: key(transaction).to_owned(),
+                ..InsiderSummary::default()
+            });
+
+            match transaction.transaction {
+                TransactionKind::Buy => {
+                    summary.buy_value += value;
+                    summary.buy_shares += shares;
+                },
+                TransactionKind::Sale => {
+                    summary.sale_value += value;
+                    summary.sale_shares += shares;
+                },
+                _ => continue,
+            }
+            summary.net_value = summary.buy_value as i64 - summary.sale_value as i64;
+            summary.net_shares = summary.buy_shares as i64 - summary.sale_shares as i64;
+            summary.transactions += 1;
+        }
+
+        let mut summaries = summaries.into_values()
+            .filter(|s| s.transactions > 0)
+            .collect::<Vec<_>>();
+        summaries.sort_by_key(|s| std::cmp::Reverse(s.net_value));
+        summaries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::insider::Relationship;
+
+    fn transaction(ticker: &str, owner: &str, day: u32, transaction: TransactionKind, value: u64) -> InsiderTransaction {
+        let date = NaiveDate::from_ymd_opt(2023, 5, day).unwrap();
+        InsiderTransaction {
+            ticker: ticker.to_string(),
+            owner: owner.to_string(),
+            relationship: Relationship::Director,
+            date,
+            transaction,
+            cost: Some(10.0),
+            shares: Some(value / 10),
+            value: Some(value),
+            shares_total: None,
+            filed: date.and_hms_opt(18, 0, 0).unwrap(),
+            form4_url: String::new(),
+        }
+    }
+
+    fn transactions() -> Vec<InsiderTransaction> {
+        vec![
+            transaction("AAA", "Alice", 1, TransactionKind::Buy, 1000),
+            transaction("AAA", "Bob", 3, TransactionKind::Buy, 2000),
+            transaction("AAA", "Carol", 5, TransactionKind::Buy, 3000),
+            transaction("AAA", "Alice", 20, TransactionKind::Sale, 500),
+            transaction("BBB", "Dave", 10, TransactionKind::Sale, 9000),
+            transaction("BBB", "Erin", 19, TransactionKind::Buy, 100),
+            transaction("BBB", "Alice", 20, TransactionKind::OptionExercise, 7000),
+        ]
+    }
+
+    #[test]
+    fn test_by_ticker() {
+        let transactions = transactions();
+        let summaries = InsiderAnalysis::new(&transactions).by_ticker();
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].key, "AAA");
+        assert_eq!(summaries[0].net_value, 5500);
+        assert_eq!(summaries[0].transactions, 4);
+        assert_eq!(summaries[1].key, "BBB");
+        assert_eq!(summaries[1].net_value, -8900);
+        assert_eq!(summaries[1].net_shares, -890);
+    }
+
+    #[test]
+    fn test_by_owner_in_window() {
+        let transactions = transactions();
+        let mut analysis = InsiderAnalysis::new(&transactions);
+        analysis.set_window(5);
+        let summaries = analysis.by_owner();
+        assert_eq!(summaries.iter().map(|s| s.key.as_str()).collect::<Vec<_>>(), vec!["Erin", "Alice"]);
+        assert_eq!(summaries[1].net_value, -500);
+    }
+
+    #[test]
+    fn test_clusters() {
+        let transactions = transactions();
+        let analysis = InsiderAnalysis::new(&transactions);
+        let clusters = analysis.clusters(3, 7);
+        assert_eq!(clusters.len(), 1);
+        assert_eq!(clusters[0].ticker, "AAA");
+        assert_eq!(clusters[0].owners, vec!["Alice", "Bob", "Carol"]);
+        assert_eq!(clusters[0].value, 6000);
+        assert_eq!(clusters[0].end, NaiveDate::from_ymd_opt(2023, 5, 5).unwrap());
+        assert!(analysis.clusters(3, 3).is_empty());
+        assert_eq!(analysis.clusters(1, 3).len(), 2);
+    }
+}
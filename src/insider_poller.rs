@@ -0,0 +1,343 @@
+use crate::common::Scrape;
+use crate::insider::{Insider, InsiderTransaction};
+use crate::web_scraper::write_file_atomic;
+use chrono::{Duration as ChronoDuration, NaiveDate, NaiveDateTime};
+use futures::stream::{self, Stream};
+use serde_json::{json, Value};
+use std::collections::{BTreeSet, HashSet, VecDeque};
+use std::time::Duration;
+
+const TIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// The stable key identifying an insider transaction across scrapes
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TransactionKey {
+    /// ticker of the company
+    pub ticker: String,
+    /// name of the insider
+    pub owner: String,
+    /// date of the transaction
+    pub date: NaiveDate,
+    /// number of shares traded
+    pub shares: Option<u64>,
+    /// time when the SEC Form 4 was filed
+    pub filed: NaiveDateTime,
+}
+
+impl From<&InsiderTransaction> for TransactionKey {
+    fn from(transaction: &InsiderTransaction) -> Self {
+        TransactionKey {
+            ticker: transaction.ticker.to_owned(),
+            owner: transaction.owner.to_owned(),
+            date: transaction.date,
+            shares: transaction.shares,
+            filed: transaction.filed,
+        }
+    }
+}
+
+impl TransactionKey {
+
+    // helper function to convert the key to a JSON array
+    fn to_json(&self) -> Value {
+        json!([
+            self.ticker,
+            self.owner,
+            self.date.format(DATE_FORMAT).to_string(),
+            self.shares,
+            self.filed.format(TIME_FORMAT).to_string(),
+        ])
+    }
+
+    // helper function to convert a JSON array back to the key
+    fn from_json(value: &Value) -> Option<Self> {
+        let [ticker, owner, date, shares, filed] = value.as_array()?.as_slice() else {
+            return None;
+        };
+
+        Some(TransactionKey {
+            ticker: ticker.as_str()?.to_string(),
+            owner: owner.as_str()?.to_string(),
+            date: NaiveDate::parse_from_str(date.as_str()?, DATE_FORMAT).ok()?,
+            shares: shares.as_u64(),
+            filed: NaiveDateTime::parse_from_str(filed.as_str()?, TIME_FORMAT).ok()?,
+        })
+    }
+}
+
+/// This struct polls `Insider` repeatedly and emits only the transactions that were not seen before.
+///
+/// The high-water mark is the latest filing time seen. Filings can show up on finviz later than
+/// their filing time, so the keys filed within the lookback (1 day by default) before the high-water
+/// mark are remembered as well. With `set_state_file` the state is persisted as JSON after every
+/// poll, and loaded when the polling starts, so a restarted poller does not emit the same
+/// transactions again.
+///
+/// # Example
+///
+/// ```no_run
+/// use finviz_rs::{
+///     insider::{Insider, InsiderType},
+///     insider_poller::InsiderPoller,
+/// };
+/// use futures::StreamExt;
+/// use std::time::Duration;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(),Box<dyn std::error::Error>>{
+///     let mut poller = InsiderPoller::new(Insider::new(InsiderType::Latest));
+///     poller.set_interval(Duration::from_secs(60))
+///         .set_state_file("insider_state.json");
+///
+///     let mut transactions = Box::pin(poller.stream());
+///     while let Some(transaction) = transactions.next().await {
+///         match transaction {
+///             Ok(t) => println!("{} {} {} {:?}", t.ticker, t.owner, t.transaction, t.value),
+///             Err(e) => eprintln!("Failed to poll insider trading: {}", e),
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+///
+/// The above example demonstrates how to print every new transaction of the latest insider trading once a minute.
+pub struct InsiderPoller {
+    insider: Insider,
+    interval: Duration,
+    lookback: ChronoDuration,
+    state_file: Option<String>,
+    high_water_mark: Option<NaiveDateTime>,
+    seen: BTreeSet<TransactionKey>,
+    skipped_rows: Vec<String>,
+}
+
+impl InsiderPoller {
+
+    /// Creates a new `InsiderPoller` of the `Insider`, polling every 5 minutes.
+    pub fn new(insider: Insider) -> Self {
+        Self {
+            insider,
+            interval: Duration::from_secs(300),
+            lookback: ChronoDuration::days(1),
+            state_file: None,
+            high_water_mark: None,
+            seen: BTreeSet::new(),
+            skipped_rows: Vec::new(),
+        }
+    }
+
+    /// Sets the interval between two polls.
+    pub fn set_interval(&mut self, interval: Duration) -> &mut Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Sets how long before the high-water mark the transactions are still remembered.
+    pub fn set_lookback(&mut self, lookback: ChronoDuration) -> &mut Self {
+        self.lookback = lookback;
+        self
+    }
+
+    /// Sets the JSON file the high-water mark is persisted to between runs.
+    pub fn set_state_file(&mut self, file_path: &str) -> &mut Self {
+        self.state_file = Some(file_path.to_string());
+        self
+    }
+
+    /// Returns the latest filing time seen.
+    pub fn high_water_mark(&self) -> Option<NaiveDateTime> {
+        self.high_water_mark
+    }
+
+    /// Loads the state from the state file, if it is set and exists.
+    pub fn load_state(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(file_path) = &self.state_file else {
+            return Ok(());
+        };
+        let content = match std::fs::read_to_string(file_path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+        self.set_state_json(&serde_json::from_str(&content)?)
+    }
+
+    /// Returns the errors of the rows skipped by the last poll, because they could not be converted to
+    /// `InsiderTransaction`.
+    pub fn skipped_rows(&self) -> &[String] {
+        &self.skipped_rows
+    }
+
+    /// Scrapes the insider trading once, and returns the new transactions ordered by filing time. The
+    /// rows that cannot be converted are skipped, see `skipped_rows`, so a malformed row does not stop
+    /// the high-water mark from advancing.
+    pub async fn poll(&mut self) -> Result<Vec<InsiderTransaction>, Box<dyn std::error::Error>> {
+        let (transactions, errors) = self.insider.parse_transactions(&self.insider.scrape().await?);
+        self.skipped_rows = errors.iter().map(|e| e.to_string()).collect();
+        let new_transactions = self.filter_new(transactions);
+        self.save_state()?;
+        Ok(new_transactions)
+    }
+
+    /// Converts the poller into a stream of the new transactions, which polls every interval forever.
+    /// The state file is loaded before the first poll, and an error of a poll is emitted without
+    /// ending the stream. A skipped row is emitted as an error too, once per distinct error rather
+    /// than on every poll that still sees it.
+    pub fn stream(mut self) -> impl Stream<Item = Result<InsiderTransaction, Box<dyn std::error::Error>>> {
+        let loaded = self.load_state();
+        let pending: VecDeque<Result<InsiderTransaction, Box<dyn std::error::Error>>> = match loaded {
+            Ok(()) => VecDeque::new(),
+            Err(e) => VecDeque::from([Err(e)]),
+        };
+
+        let reported = HashSet::new();
+        stream::unfold((self, pending, reported, true), |(mut poller, mut pending, mut reported, mut first)| async move {
+            while pending.is_empty() {
+                if !first {
+                    tokio::time::sleep(poller.interval).await;
+                }
+                first = false;
+                match poller.poll().await {
+                    Ok(transactions) => pending.extend(poller.stream_items(transactions, &mut reported)),
+                    Err(e) => pending.push_back(Err(e)),
+                }
+            }
+            let item = pending.pop_front()?;
+            Some((item, (poller, pending, reported, first)))
+        })
+    }
+
+    // helper function to emit the skipped rows not reported yet as errors, followed by the new transactions
+    fn stream_items(&self, transactions: Vec<InsiderTransaction>, reported: &mut HashSet<String>)
+        -> Vec<Result<InsiderTransaction, Box<dyn std::error::Error>>> {
+        self.skipped_rows.iter()
+            .filter(|row| reported.insert(row.to_string()))
+            .map(|row| Err(format!("Skipped a row: {}", row).into()))
+            .chain(transactions.into_iter().map(Ok))
+            .collect()
+    }
+
+    // helper function to keep the unseen transactions, and advance the high-water mark
+    fn filter_new(&mut self, transactions: Vec<InsiderTransaction>) -> Vec<InsiderTransaction> {
+        let cutoff = self.high_water_mark.map(|mark| mark - self.lookback);
+        let mut new_transactions = transactions.into_iter()
+            .filter(|t| cutoff.is_none_or(|cutoff| t.filed >= cutoff))
+            .filter(|t| self.seen.insert(TransactionKey::from(t)))
+            .collect::<Vec<_>>();
+        new_transactions.sort_by_key(|t| t.filed);
+
+        if let Some(latest) = new_transactions.last().map(|t| t.filed) {
+            if self.high_water_mark.is_none_or(|mark| latest > mark) {
+                self.high_water_mark = Some(latest);
+            }
+        }
+        if let Some(mark) = self.high_water_mark {
+            let cutoff = mark - self.lookback;
+            self.seen.retain(|key| key.filed >= cutoff);
+        }
+        new_transactions
+    }
+
+    // helper function to write the state to the state file, if it is set
+    fn save_state(&self) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(file_path) = &self.state_file {
+            let content = serde_json::to_string_pretty(&self.state_json())?;
+            write_file_atomic(file_path, content.as_bytes())?;
+        }
+        Ok(())
+    }
+
+    // helper function to convert the state to JSON
+    fn state_json(&self) -> Value {
+        json!({
+            "high_water_mark": self.high_water_mark.map(|mark| mark.format(TIME_FORMAT).to_string()),
+            "seen": self.seen.iter().map(TransactionKey::to_json).collect::<Vec<_>>(),
+        })
+    }
+
+    // helper function to restore the state from JSON
+    fn set_state_json(&mut self, state: &Value) -> Result<(), Box<dyn std::error::Error>> {
+        self.high_water_mark = match &state["high_water_mark"] {
+            Value::Null => None,
+            mark => Some(NaiveDateTime::parse_from_str(mark.as_str().ok_or("Invalid high-water mark")?, TIME_FORMAT)?),
+        };
+        self.seen = state["seen"].as_array()
+            .map(|keys| keys.iter().filter_map(TransactionKey::from_json).collect())
+            .unwrap_or_default();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::insider::{InsiderType, Relationship, TransactionKind};
+
+    fn transaction(ticker: &str, shares: u64, filed_day: u32, filed_hour: u32) -> InsiderTransaction {
+        let date = NaiveDate::from_ymd_opt(2023, 5, 1).unwrap();
+        InsiderTransaction {
+            ticker: ticker.to_string(),
+            owner: "Alice".to_string(),
+            relationship: Relationship::Director,
+            date,
+            transaction: TransactionKind::Buy,
+            cost: Some(10.0),
+            shares: Some(shares),
+            value: Some(shares * 10),
+            shares_total: None,
+            filed: NaiveDate::from_ymd_opt(2023, 5, filed_day).unwrap().and_hms_opt(filed_hour, 0, 0).unwrap(),
+            form4_url: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_filter_new() {
+        let mut poller = InsiderPoller::new(Insider::new(InsiderType::Latest));
+        let first = poller.filter_new(vec![transaction("BBB", 200, 2, 18), transaction("AAA", 100, 2, 9)]);
+        assert_eq!(first.iter().map(|t| t.ticker.as_str()).collect::<Vec<_>>(), vec!["AAA", "BBB"]);
+        assert_eq!(poller.high_water_mark(), Some(first[1].filed));
+
+        // the same page again, plus a late filing within the lookback and one before it
+        let second = poller.filter_new(vec![
+            transaction("CCC", 300, 3, 8),
+            transaction("BBB", 200, 2, 18),
+            transaction("AAA", 100, 2, 9),
+            transaction("DDD", 400, 2, 12),
+            transaction("EEE", 500, 1, 12),
+        ]);
+        assert_eq!(second.iter().map(|t| t.ticker.as_str()).collect::<Vec<_>>(), vec!["DDD", "CCC"]);
+        assert_eq!(poller.high_water_mark(), Some(second[1].filed));
+    }
+
+    #[test]
+    fn test_state_round_trip() {
+        let mut poller = InsiderPoller::new(Insider::new(InsiderType::Latest));
+        poller.filter_new(vec![transaction("AAA", 100, 2, 9), transaction("BBB", 200, 2, 18)]);
+        let state = poller.state_json();
+        assert_eq!(state["high_water_mark"], "2023-05-02T18:00:00");
+
+        let mut restored = InsiderPoller::new(Insider::new(InsiderType::Latest));
+        restored.set_state_json(&state).unwrap();
+        assert_eq!(restored.high_water_mark(), poller.high_water_mark());
+        assert_eq!(restored.seen, poller.seen);
+        assert!(restored.filter_new(vec![transaction("AAA", 100, 2, 9)]).is_empty());
+    }
+
+    #[test]
+    fn test_stream_items() {
+        let mut poller = InsiderPoller::new(Insider::new(InsiderType::Latest));
+        let mut reported = HashSet::new();
+        poller.skipped_rows = vec!["Invalid date of insider trading: abc".to_string()];
+        let items = poller.stream_items(vec![transaction("AAA", 100, 2, 9)], &mut reported);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].as_ref().unwrap_err().to_string(), "Skipped a row: Invalid date of insider trading: abc");
+        assert_eq!(items[1].as_ref().unwrap().ticker, "AAA");
+
+        // the same malformed row on the next poll is not reported again
+        poller.skipped_rows.push("Invalid filing time of insider trading: xyz".to_string());
+        let items = poller.stream_items(Vec::new(), &mut reported);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].as_ref().unwrap_err().to_string(), "Skipped a row: Invalid filing time of insider trading: xyz");
+    }
+}
@@ -19,18 +19,38 @@ pub mod screener;
 
 /// a module includes `News` struct for news and blogs scraping
 pub mod news;
+/// a module includes `SentimentAnalyzer` struct for lexicon-based headline sentiment (requires the "sentiment" feature)
+#[cfg(feature = "sentiment")]
+pub mod sentiment;
+/// a module includes `TickerExtractor` struct to find the tickers mentioned in news headlines
+pub mod mentions;
 /// a module includes `Insider` struct for insider data scraping
 pub mod insider;
+/// a module includes `InsiderAnalysis` struct for aggregating insider trading and detecting cluster buys
+pub mod insider_analysis;
+/// a module includes `InsiderPoller` struct for polling new insider trading incrementally
+pub mod insider_poller;
 /// a module includes `Forex` struct for forex data scraping
 pub mod forex;
 /// a module includes `Crypto` struct for cryptocurrency data scraping
 pub mod crypto;
+/// a module includes `PerformanceRow` struct for the typed performance of `Forex` and `Crypto`
+pub mod performance;
 /// a module includes `Future` struct for futures data scraping
 pub mod future;
 /// a module includes `Group` struct for group data scraping 
 pub mod group;
+/// a module includes `SectorRotation` struct for ranking groups across timeframes and classifying their rotation
+pub mod rotation;
 /// a module includes `Tickers` struct for stock data scraping and chart downloading
 pub mod tickers;
+/// a module includes `ChartRequest` struct to configure the chart images of `Tickers`
+pub mod chart;
+/// a module includes `Montage` struct to compose chart images into a grid (requires the "charts_montage" feature)
+#[cfg(feature = "charts_montage")]
+pub mod montage;
+/// a module includes `Market` struct for market breadth and signal tables scraping
+pub mod market;
 
 /// a module includes helper function to convert scraped data to different types of output 
 pub mod output;
@@ -40,3 +60,6 @@ pub mod common;
 
 /// utility functions for scraping web content
 pub mod web_scraper;
+
+/// helper functions to convert scraped strings to numeric values
+pub mod parse;
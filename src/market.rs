@@ -0,0 +1,222 @@
+use scraper::{ElementRef, Html, Selector};
+use crate::web_scraper::get_html_body;
+use crate::common::{DisplayString, Scrape};
+use crate::signal_type::SignalType;
+use crate::parse::{parse_count, parse_number};
+use std::collections::BTreeMap;
+use async_trait::async_trait;
+
+/// `Market` struct provides a way to scrape the finviz home page for market breadth and signal tables
+///
+/// # Example
+///
+/// ```
+/// use finviz_rs::{
+///     market::Market,
+///     signal_type::SignalType,
+///     common::Scrape,
+/// };
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(),Box<dyn std::error::Error>>{
+///     let r = Market::default()
+///         .scrape().await?;
+///     println!("{:?}", r.breadth.advancing);
+///     println!("{:?}", r.signal(SignalType::TopGainers));
+///     Ok(())
+/// }
+/// ```
+///
+/// The above example demonstrates how to retrive the market breadth and the tickers of a signal from the home page.
+pub struct Market {}
+
+/// A single market breadth figure, e.g. the number and percentage of advancing stocks
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BreadthStat {
+    /// number of stocks
+    pub count: u64,
+    /// percentage of all stocks
+    pub percent: f64,
+}
+
+/// Market breadth figures shown on the top of the home page
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MarketBreadth {
+    /// advancing stocks
+    pub advancing: BreadthStat,
+    /// declining stocks
+    pub declining: BreadthStat,
+    /// stocks making a new high
+    pub new_high: BreadthStat,
+    /// stocks making a new low
+    pub new_low: BreadthStat,
+    /// stocks above SMA50
+    pub above_sma50: BreadthStat,
+    /// stocks below SMA50
+    pub below_sma50: BreadthStat,
+    /// stocks above SMA200
+    pub above_sma200: BreadthStat,
+    /// stocks below SMA200
+    pub below_sma200: BreadthStat,
+}
+
+/// a struct to store scraping data from `Market`
+#[derive(Debug, Default)]
+pub struct MarketData {
+    /// data field to store the market breadth
+    pub breadth: MarketBreadth,
+    /// data field to store the tickers of each signal table, keyed by the signal name shown on the
+    /// page (e.g. "Top Gainers")
+    pub signals: BTreeMap<String, Vec<String>>,
+}
+
+impl MarketData {
+
+    /// Returns the tickers listed for the given signal, if the home page shows it
+    pub fn signal(&self, signal_type: SignalType) -> Option<&Vec<String>> {
+        self.signals.get(signal_type.to_display_string())
+    }
+}
+
+impl Default for Market {
+
+    /// Create new instance of `Market` using default constructor
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Market {
+    const BASE_URL: &'static str = "https://finviz.com/";
+
+    /// Create new instance of `Market`
+    pub fn new() -> Self {
+        Self{}
+    }
+}
+
+#[async_trait]
+impl Scrape<MarketData> for Market {
+
+    /// Scrapes the home page and return `MarketData` on success, or `Box<dyn std::error::Error>` on failure
+    async fn scrape(&self) -> Result<MarketData, Box<dyn std::error::Error>> {
+        let body = get_html_body(Market::BASE_URL).await?;
+        parse_market(&Html::parse_document(&body))
+    }
+}
+
+// helper function to parse the home page and return `MarketData` on success, or `Box<dyn std::error::Error>` on failure
+fn parse_market(document: &Html) -> Result<MarketData, Box<dyn std::error::Error>> {
+    let stats_selector = Selector::parse("div.market-stats")?;
+    let left_selector = Selector::parse(".market-stats_labels_left")?;
+    let right_selector = Selector::parse(".market-stats_labels_right")?;
+
+    let mut breadth = MarketBreadth::default();
+    let mut found = false;
+    for stats in document.select(&stats_selector) {
+        for side in stats.select(&left_selector).chain(stats.select(&right_selector)) {
+            let (label, stat) = parse_breadth_label(side);
+            let field = match label.as_str() {
+                "advancing" => &mut breadth.advancing,
+                "declining" => &mut breadth.declining,
+                "new high" => &mut breadth.new_high,
+                "new low" => &mut breadth.new_low,
+                "above sma50" => &mut breadth.above_sma50,
+                "below sma50" => &mut breadth.below_sma50,
+                "above sma200" => &mut breadth.above_sma200,
+                "below sma200" => &mut breadth.below_sma200,
+                _ => continue,
+            };
+            *field = stat;
+            found = true;
+        }
+    }
+    if !found {
+        return Err("Cannot find market breadth".into());
+    }
+
+    Ok(MarketData { breadth, signals: parse_signals(document)? })
+}
+
+// helper function to split a breadth label such as "Advancing 59.4% (3276)" into its name and figures
+fn parse_breadth_label(side: ElementRef<'_>) -> (String, BreadthStat) {
+    let text = side.text().collect::<Vec<_>>().join(" ");
+    let mut stat = BreadthStat::default();
+    let mut words = Vec::new();
+
+    for token in text.split_whitespace() {
+        if token.starts_with('(') && token.ends_with(')') {
+            stat.count = parse_count(token.trim_matches(|c| c == '(' || c == ')')).unwrap_or_default();
+        } else if token.ends_with('%') {
+            stat.percent = parse_number(token).unwrap_or_default();
+        } else {
+            words.push(token.to_lowercase());
+        }
+    }
+
+    (words.join(" "), stat)
+}
+
+// helper function to collect the tickers of each signal table; the signal name is only shown on
+// the first row of each signal, so it is carried over to the following rows
+fn parse_signals(document: &Html) -> Result<BTreeMap<String, Vec<String>>, Box<dyn std::error::Error>> {
+    let row_selector = Selector::parse("table.t-home-table tr")?;
+    let ticker_selector = Selector::parse("a[href*=\"quote.ashx?t=\"]")?;
+    let signal_selector = Selector::parse("a[href*=\"screener.ashx?s=\"]")?;
+
+    let mut signals: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut current_signal = None;
+    for row in document.select(&row_selector) {
+        if let Some(signal) = row.select(&signal_selector).next() {
+            current_signal = Some(signal.text().collect::<String>().trim().to_owned());
+        }
+
+        if let (Some(signal), Some(ticker)) = (&current_signal, row.select(&ticker_selector).next()) {
+            signals
+                .entry(signal.to_owned())
+                .or_default()
+                .push(ticker.text().collect::<String>().trim().to_owned());
+        }
+    }
+
+    Ok(signals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOME_PAGE: &str = r#"
+        <div class="market-stats">
+            <div class="market-stats_labels">
+                <div class="market-stats_labels_left"><p>Advancing</p><p>59.4% <span>(3276)</span></p></div>
+                <div class="market-stats_labels_right"><p><span>(2033)</span> 36.9%</p><p>Declining</p></div>
+            </div>
+        </div>
+        <div class="market-stats">
+            <div class="market-stats_labels">
+                <div class="market-stats_labels_left"><p>Above SMA200</p><p>48.1% <span>(2,650)</span></p></div>
+                <div class="market-stats_labels_right"><p><span>(2,861)</span> 51.9%</p><p>Below SMA200</p></div>
+            </div>
+        </div>
+        <table class="t-home-table">
+            <tr><th>Ticker</th><th>Last</th><th>Change</th><th>Volume</th><th>Signal</th></tr>
+            <tr><td><a href="quote.ashx?t=AYTU">AYTU</a></td><td>1.84</td><td>20.26%</td><td>102664</td>
+                <td><a href="screener.ashx?s=ta_topgainers">Top Gainers</a></td></tr>
+            <tr><td><a href="quote.ashx?t=LPCN">LPCN</a></td><td>4.31</td><td>11.04%</td><td>191855</td><td></td></tr>
+            <tr><td><a href="quote.ashx?t=GSIT">GSIT</a></td><td>5.09</td><td>3.37%</td><td>104612</td>
+                <td><a href="screener.ashx?s=ta_newhigh">New High</a></td></tr>
+        </table>
+    "#;
+
+    #[test]
+    fn test_parse_market() {
+        let r = parse_market(&Html::parse_document(HOME_PAGE)).unwrap();
+        assert_eq!(r.breadth.advancing, BreadthStat { count: 3276, percent: 59.4 });
+        assert_eq!(r.breadth.declining, BreadthStat { count: 2033, percent: 36.9 });
+        assert_eq!(r.breadth.below_sma200, BreadthStat { count: 2861, percent: 51.9 });
+        assert_eq!(r.signal(SignalType::TopGainers), Some(&vec!["AYTU".to_string(), "LPCN".to_string()]));
+        assert_eq!(r.signal(SignalType::NewHigh), Some(&vec!["GSIT".to_string()]));
+        assert_eq!(r.signal(SignalType::Oversold), None);
+    }
+}
@@ -0,0 +1,320 @@
+use crate::common::TableData;
+use crate::news::{NewsData, NewsItem};
+use std::collections::{HashMap, HashSet};
+
+/// Uppercase words of headlines that are also tickers, which are only matched as cashtags like "$ON"
+const AMBIGUOUS_TICKERS: &[&str] = &[
+    "A", "AI", "ALL", "AM", "AN", "ARE", "AT", "BE", "BIG", "CAN", "CEO", "CFO", "DD", "EPS", "ETF",
+    "EU", "EV", "FOR", "GDP", "GO", "HAS", "IPO", "IT", "LOW", "NEW", "NOW", "NYSE", "ON", "ONE",
+    "OR", "OUT", "PM", "SEC", "SO", "TV", "UK", "US", "USA", "YOU",
+];
+
+/// Company names of a single word that are also common words of headlines, which are only matched in
+/// their original casing and not in Title Case, e.g. "Target" in "Analyst Lifts Price Target"
+const AMBIGUOUS_NAMES: &[&str] = &[
+    "ball", "best", "block", "chase", "gap", "match", "meta", "shift", "snap", "square", "target",
+    "trade", "visa",
+];
+
+/// Words at the end of company names that are dropped when matching the names, e.g. "Apple Inc."
+const COMPANY_SUFFIXES: &[&str] = &[
+    "inc", "incorporated", "corp", "corporation", "co", "company", "ltd", "limited", "plc", "llc",
+    "lp", "holdings", "holding", "group", "sa", "nv", "ag", "se", "class", "a", "b", "c", "the",
+    "adr", "ads",
+];
+
+/// This struct extracts the tickers mentioned in headlines, by matching them against a universe of
+/// tickers and company names.
+///
+/// A ticker is matched as a cashtag like "$AAPL" in any case, or as a whole uppercase word like
+/// "AAPL". Single letter tickers and tickers that are common words like "ON" or "IT" are matched as
+/// cashtags only. A company name is matched as whole words ignoring the case and the suffixes like
+/// "Inc." or "Corp.", but the first word must be capitalized, so "Target Corp" is not found in
+/// "price target". A single word name that is also a common word, like "Target" or "Snap", must also
+/// be in its original casing and not next to other capitalized words, so it is not found in Title
+/// Case headlines like "Analyst Lifts Price Target", where the casing tells nothing; the cashtag
+/// "$TGT" is found there.
+///
+/// # Example
+///
+/// ```
+/// use finviz_rs::{
+///     news::News,
+///     screener::Screener,
+///     screener_type::ScreenerType,
+///     mentions::TickerExtractor,
+///     common::Scrape,
+/// };
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(),Box<dyn std::error::Error>>{
+///     let universe = Screener::new(ScreenerType::Overview).scrape().await?;
+///     let extractor = TickerExtractor::from_table(&universe);
+///
+///     let mut news = News::default().scrape().await?;
+///     extractor.attach(&mut news);
+///     for item in news.news.iter().filter(|item| !item.tickers.is_empty()) {
+///         println!("{:?} {}", item.tickers, item.title);
+///     }
+///     Ok(())
+/// }
+/// ```
+///
+/// The above example demonstrates how to load the universe from a screener result, and label the news with the tickers mentioned.
+#[derive(Clone, Debug, Default)]
+pub struct TickerExtractor {
+    tickers: HashSet<String>,
+    // the normalized words of the company names, to the ticker and the words in the original casing
+    names: HashMap<Vec<String>, (String, Vec<String>)>,
+}
+
+impl TickerExtractor {
+
+    /// Create an extractor with an empty universe.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an extractor from a table with the header in the first row, e.g. the result of
+    /// `Screener::scrape`. The tickers are read from the "Ticker" column, and the company names from
+    /// the "Company" column if it exists.
+    pub fn from_table(table: &TableData) -> Self {
+        let mut extractor = TickerExtractor::new();
+        let Some(header) = table.first() else {
+            return extractor;
+        };
+        let Some(ticker_index) = header.iter().position(|h| h == "Ticker") else {
+            return extractor;
+        };
+        let company_index = header.iter().position(|h| h == "Company");
+
+        for row in table.iter().skip(1) {
+            if let Some(ticker) = row.get(ticker_index) {
+                let company = company_index.and_then(|index| row.get(index)).map(String::as_str);
+                extractor.add_ticker(ticker, company);
+            }
+        }
+        extractor
+    }
+
+    /// Adds a ticker to the universe, with the company name if it is known.
+    pub fn add_ticker(&mut self, ticker: &str, company: Option<&str>) -> &mut Self {
+        let ticker = ticker.trim().to_uppercase();
+        if ticker.is_empty() {
+            return self;
+        }
+        if let Some(name) = company.map(company_words).filter(|words| !words.is_empty()) {
+            let normalized = name.iter().map(|word| normalize_word(word)).collect();
+            self.names.insert(normalized, (ticker.to_owned(), name));
+        }
+        self.tickers.insert(ticker);
+        self
+    }
+
+    /// Returns the tickers mentioned in the text, in the order of the tickers and cashtags first, and
+    /// then the company names.
+    pub fn extract(&self, text: &str) -> Vec<String> {
+        let mut tickers = Vec::new();
+        let mut push = |ticker: &str| {
+            if !tickers.iter().any(|t| t == ticker) {
+                tickers.push(ticker.to_string());
+            }
+        };
+
+        for token in ticker_tokens(text) {
+            if let Some(cashtag) = token.strip_prefix('$') {
+                let cashtag = cashtag.to_uppercase();
+                if self.tickers.contains(&cashtag) {
+                    push(&cashtag);
+                }
+            } else if token.len() > 1
+                && token.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '.' || c == '-')
+                && token.chars().any(|c| c.is_ascii_uppercase())
+                && !AMBIGUOUS_TICKERS.contains(&token)
+                && self.tickers.contains(token)
+            {
+                push(token);
+            }
+        }
+
+        let words = word_spans(text);
+        let normalized = words.iter().map(|(_, word)| normalize_word(word)).collect::<Vec<_>>();
+        let max_len = self.names.keys().map(Vec::len).max().unwrap_or(0);
+        // whether the word at the index is capitalized and only separated by spaces from the word at `other`
+        let capitalized_next_to = |index: Option<usize>, other: usize| index.and_then(|i| words.get(i)).is_some_and(|(start, word)| {
+            let (other_start, other_word) = words[other];
+            let between = if *start < other_start { &text[start + word.len()..other_start] } else { &text[other_start + other_word.len()..*start] };
+            word.starts_with(char::is_uppercase) && between.chars().all(char::is_whitespace)
+        });
+
+        for start in 0..words.len() {
+            if !words[start].1.starts_with(|c: char| c.is_uppercase() || c.is_ascii_digit()) {
+                continue;
+            }
+            // the longest company name starting at the word
+            let matched = (1..=max_len.min(words.len() - start)).rev()
+                .find_map(|n| {
+                    let (ticker, original) = self.names.get(&normalized[start..start + n])?;
+                    let is_ambiguous = n == 1 && AMBIGUOUS_NAMES.contains(&normalized[start].as_str()) && (
+                        without_possessive(words[start].1) != without_possessive(&original[0])
+                        || capitalized_next_to(start.checked_sub(1), start)
+                        || capitalized_next_to(Some(start + 1), start)
+                    );
+                    (!is_ambiguous).then_some(ticker)
+                });
+            if let Some(ticker) = matched {
+                push(ticker);
+            }
+        }
+
+        tickers
+    }
+
+    /// Adds the tickers mentioned in the titles to the tickers of the news and blogs.
+    pub fn attach(&self, data: &mut NewsData) {
+        self.attach_items(&mut data.news);
+        self.attach_items(&mut data.blogs);
+    }
+
+    /// Adds the tickers mentioned in the titles to the tickers of the items.
+    pub fn attach_items(&self, items: &mut [NewsItem]) {
+        for item in items.iter_mut() {
+            for ticker in self.extract(&item.title) {
+                if !item.tickers.contains(&ticker) {
+                    item.tickers.push(ticker);
+                }
+            }
+        }
+    }
+}
+
+// helper function to split a text into the candidates of tickers and cashtags, e.g. "$AAPL" or "BRK.B"
+fn ticker_tokens(text: &str) -> impl Iterator<Item = &str> {
+    text.split(|c: char| !(c.is_alphanumeric() || c == '$' || c == '.' || c == '-'))
+        .map(|token| token.trim_end_matches(['.', '-']))
+        .filter(|token| !token.is_empty())
+}
+
+// helper function of the characters of the words in company names, e.g. "AT&T" or "McDonald's"
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '&' || c == '\''
+}
+
+// helper function to split a text into the words of company names with their start index
+fn word_spans(text: &str) -> Vec<(usize, &str)> {
+    let mut spans = Vec::new();
+    let mut start = None;
+    for (i, c) in text.char_indices().chain(std::iter::once((text.len(), ' '))) {
+        match (start, is_word_char(c)) {
+            (None, true) => start = Some(i),
+            (Some(s), false) => {
+                spans.push((s, &text[s..i]));
+                start = None;
+            },
+            _ => {},
+        }
+    }
+    spans
+}
+
+// helper function to compare the words ignoring the case and the possessive
+fn normalize_word(word: &str) -> String {
+    without_possessive(&word.to_lowercase()).to_string()
+}
+
+// helper function to compare the words ignoring the possessive, e.g. "McDonald's"
+fn without_possessive(word: &str) -> &str {
+    word.strip_suffix("'s").unwrap_or(word).trim_matches('\'')
+}
+
+// helper function to convert a company name into the words to match, without the suffixes
+fn company_words(company: &str) -> Vec<String> {
+    let mut words = company.split(|c: char| !is_word_char(c))
+        .filter(|word| !word.is_empty())
+        .map(String::from)
+        .collect::<Vec<_>>();
+
+    while words.last().is_some_and(|word| COMPANY_SUFFIXES.contains(&normalize_word(word).as_str())) {
+        words.pop();
+    }
+    if words.first().is_some_and(|word| normalize_word(word) == "the") {
+        words.remove(0);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn extractor() -> TickerExtractor {
+        let table: TableData = vec![
+            vec!["Ticker", "Company", "Sector"],
+            vec!["AAPL", "Apple Inc.", "Technology"],
+            vec!["BAC", "Bank of America Corp.", "Financial"],
+            vec!["T", "AT&T Inc.", "Communication Services"],
+            vec!["BRK-B", "Berkshire Hathaway Inc. Class B", "Financial"],
+            vec!["ON", "ON Semiconductor Corp.", "Technology"],
+            vec!["TGT", "Target Corp", "Consumer Defensive"],
+            vec!["MCD", "McDonald's Corp", "Consumer Cyclical"],
+        ].into_iter().map(|row| row.into_iter().map(String::from).collect()).collect();
+        TickerExtractor::from_table(&table)
+    }
+
+    #[test]
+    fn test_company_words() {
+        assert_eq!(company_words("Alphabet Inc. Class A"), vec!["Alphabet"]);
+        assert_eq!(company_words("The Walt Disney Company"), vec!["Walt", "Disney"]);
+        assert_eq!(company_words("AT&T Inc."), vec!["AT&T"]);
+    }
+
+    #[test]
+    fn test_extract_tickers() {
+        let extractor = extractor();
+        assert_eq!(extractor.extract("AAPL, BAC lead the Dow"), vec!["AAPL", "BAC"]);
+        assert_eq!(extractor.extract("Why $aapl and (BRK-B) rallied."), vec!["AAPL", "BRK-B"]);
+        assert!(extractor.extract("Aapl is not a ticker, neither is XYZ").is_empty());
+        assert!(extractor.extract("Stocks move ON news, T-bills rally").is_empty());
+        assert_eq!(extractor.extract("$ON and $T rise"), vec!["ON", "T"]);
+    }
+
+    #[test]
+    fn test_extract_companies() {
+        let extractor = extractor();
+        assert_eq!(extractor.extract("Apple's iPhone sales beat"), vec!["AAPL"]);
+        assert_eq!(extractor.extract("BANK OF AMERICA and AT&T report"), vec!["BAC", "T"]);
+        assert_eq!(extractor.extract("McDonald's raises prices"), vec!["MCD"]);
+        assert_eq!(extractor.extract("Target cuts guidance"), vec!["TGT"]);
+        assert!(extractor.extract("Analyst lifts price target; apple pie").is_empty());
+    }
+
+    #[test]
+    fn test_extract_title_case() {
+        let extractor = extractor();
+        assert!(extractor.extract("Analyst Lifts Price Target").is_empty());
+        assert!(extractor.extract("Why Target Stock Fell Today").is_empty());
+        assert!(extractor.extract("TARGET CUTS GUIDANCE").is_empty());
+        assert_eq!(extractor.extract("$TGT Price Target Raised"), vec!["TGT"]);
+        assert_eq!(extractor.extract("Target, Apple report earnings"), vec!["TGT", "AAPL"]);
+        assert_eq!(extractor.extract("Bank Of America Raises Price Target"), vec!["BAC"]);
+        assert_eq!(extractor.extract("Apple Stock Soars"), vec!["AAPL"]);
+        assert_eq!(extractor.extract("Why Apple Stock Is Down Today"), vec!["AAPL"]);
+        assert_eq!(extractor.extract("APPLE SHARES JUMP"), vec!["AAPL"]);
+    }
+
+    #[test]
+    fn test_attach() {
+        use chrono::TimeZone;
+        let item = NewsItem {
+            published: chrono_tz::America::New_York.with_ymd_and_hms(2023, 5, 15, 9, 30, 0).unwrap(),
+            title: "Apple and $BAC rise".to_string(),
+            source: "www.wsj.com".to_string(),
+            url: "https://www.wsj.com/a".to_string(),
+            kind: crate::news::NewsKind::News,
+            tickers: vec!["BAC".to_string()],
+        };
+        let mut data = NewsData { news: vec![item], blogs: Vec::new() };
+        extractor().attach(&mut data);
+        assert_eq!(data.news[0].tickers, vec!["BAC", "AAPL"]);
+    }
+}
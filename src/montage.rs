@@ -0,0 +1,261 @@
+use crate::chart::ChartManifest;
+use crate::common::TableData;
+use crate::web_scraper::write_file_atomic;
+use image::{imageops, ImageOutputFormat, Rgba, RgbaImage};
+use std::io::Cursor;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+/// A chart image placed in a `Montage`, labeled by its ticker and change
+#[derive(Debug, Clone)]
+pub struct MontageTile {
+    /// path of the chart image
+    pub image_path: String,
+    /// ticker shown in the caption
+    pub ticker: String,
+    /// change shown in the caption, e.g. "-3.85%"
+    pub change: Option<String>,
+}
+
+/// This struct composes chart images into a labeled grid PNG. It is only available when the
+/// "charts_montage" feature is enabled.
+///
+/// # Example
+///
+/// ```
+/// use finviz_rs::{
+///     chart::{ChartDownloader, ChartRequest, tickers_from_table},
+///     montage::{Montage, tiles_from_manifest},
+///     screener::Screener,
+///     common::Scrape,
+/// };
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(),Box<dyn std::error::Error>> {
+///     let table = Screener::default().scrape().await?;
+///     let manifest = ChartDownloader::new(ChartRequest::new())
+///         .download(&tickers_from_table(&table), "charts", |_| {}).await;
+///     Montage::new(4)
+///         .save(&tiles_from_manifest(&manifest, &table), "charts/montage.png")?;
+///     Ok(())
+/// }
+/// ```
+///
+/// The above example demonstrates how to publish the charts of a screener result as one image.
+pub struct Montage {
+    columns: usize,
+    font_scale: u32,
+}
+
+impl Default for Montage {
+
+    /// Creates a new instance of `Montage` using the default constructor
+    fn default() -> Self {
+        Self::new(4)
+    }
+}
+
+impl Montage {
+    const PADDING: u32 = 4;
+    const BACKGROUND: Rgba<u8> = Rgba([255, 255, 255, 255]);
+    const TEXT: Rgba<u8> = Rgba([0, 0, 0, 255]);
+    const GAIN: Rgba<u8> = Rgba([0, 140, 0, 255]);
+    const LOSS: Rgba<u8> = Rgba([200, 0, 0, 255]);
+
+    /// Creates a new `Montage` with the number of columns of the grid.
+    pub fn new(columns: usize) -> Self {
+        Self { columns: columns.max(1), font_scale: 2 }
+    }
+
+    /// Sets the scale of the caption font, which is 5x7 pixels per character at scale 1.
+    pub fn set_font_scale(&mut self, font_scale: u32) -> &mut Self {
+        self.font_scale = font_scale.max(1);
+        self
+    }
+
+    /// Composes the tiles into a grid image, each cell showing the caption above the chart.
+    pub fn compose(&self, tiles: &[MontageTile]) -> Result<RgbaImage> {
+        if tiles.is_empty() {
+            return Err("No chart to compose".into());
+        }
+
+        let images = tiles.iter()
+            .map(|tile| {
+                image::open(&tile.image_path)
+                    .map(|image| image.to_rgba8())
+                    .map_err(|err| format!("Failed to open {}: {}", tile.image_path, err).into())
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let caption_height = 7 * self.font_scale + 2 * Montage::PADDING;
+        let cell_width = images.iter().map(RgbaImage::width).max().unwrap_or_default() + 2 * Montage::PADDING;
+        let cell_height = images.iter().map(RgbaImage::height).max().unwrap_or_default() + caption_height + Montage::PADDING;
+        let columns = self.columns.min(tiles.len());
+        let rows = tiles.len().div_ceil(columns);
+
+        let mut canvas = RgbaImage::from_pixel(cell_width * columns as u32, cell_height * rows as u32, Montage::BACKGROUND);
+        for (i, (tile, image)) in tiles.iter().zip(images.iter()).enumerate() {
+            let x = (i % columns) as u32 * cell_width + Montage::PADDING;
+            let y = (i / columns) as u32 * cell_height + Montage::PADDING;
+
+            let mut caption_x = self.draw_text(&mut canvas, &tile.ticker, x, y, Montage::TEXT);
+            if let Some(change) = &tile.change {
+                let color = if change.starts_with('-') { Montage::LOSS } else { Montage::GAIN };
+                caption_x = self.draw_text(&mut canvas, " ", caption_x, y, color);
+                self.draw_text(&mut canvas, change, caption_x, y, color);
+            }
+            imageops::overlay(&mut canvas, image, x as i64, (y + caption_height - Montage::PADDING) as i64);
+        }
+
+        Ok(canvas)
+    }
+
+    /// Composes the tiles into a grid image and saves it as PNG to the file path, return the file
+    /// path on success.
+    pub fn save(&self, tiles: &[MontageTile], file_path: &str) -> Result<String> {
+        let mut data = Cursor::new(Vec::new());
+        self.compose(tiles)?.write_to(&mut data, ImageOutputFormat::Png)?;
+        write_file_atomic(file_path, data.get_ref())?;
+        Ok(file_path.to_owned())
+    }
+
+    // helper function to draw the text with the built-in bitmap font, return the x position after the text
+    fn draw_text(&self, canvas: &mut RgbaImage, text: &str, x: u32, y: u32, color: Rgba<u8>) -> u32 {
+        let mut x = x;
+        for c in text.chars() {
+            for (row, bits) in glyph(c).iter().enumerate() {
+                for col in 0..5 {
+                    if bits & (0b10000 >> col) == 0 {
+                        continue;
+                    }
+                    for dy in 0..self.font_scale {
+                        for dx in 0..self.font_scale {
+                            let (px, py) = (x + col * self.font_scale + dx, y + row as u32 * self.font_scale + dy);
+                            if px < canvas.width() && py < canvas.height() {
+                                canvas.put_pixel(px, py, color);
+                            }
+                        }
+                    }
+                }
+            }
+            x += 6 * self.font_scale;
+        }
+        x
+    }
+}
+
+/// Returns the tiles of the successfully downloaded charts in a `ChartManifest`, in the order of the
+/// table. The change in the caption is taken from the column named "Change" of the table, if present.
+pub fn tiles_from_manifest(manifest: &ChartManifest, table: &TableData) -> Vec<MontageTile> {
+    let header = table.first();
+    let column = |name: &str| header.and_then(|header| header.iter().position(|h| h == name));
+    let (ticker_index, change_index) = (column("Ticker").unwrap_or(0), column("Change"));
+
+    let mut tiles = Vec::new();
+    for row in table.iter() {
+        let Some(ticker) = row.get(ticker_index) else { continue };
+        if let Some((_, path)) = manifest.succeeded.iter().find(|(t, _)| t == ticker) {
+            tiles.push(MontageTile {
+                image_path: path.to_owned(),
+                ticker: ticker.to_owned(),
+                change: change_index.and_then(|i| row.get(i)).cloned(),
+            });
+        }
+    }
+    tiles
+}
+
+// 5x7 bitmap font of the characters used in captions, lower case letters are drawn as upper case
+fn glyph(c: char) -> [u8; 7] {
+    match c.to_ascii_uppercase() {
+        '0' => [0b01110, 0b10001, 0b10011, 0b10101, 0b11001, 0b10001, 0b01110],
+        '1' => [0b00100, 0b01100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        '2' => [0b01110, 0b10001, 0b00001, 0b00010, 0b00100, 0b01000, 0b11111],
+        '3' => [0b11111, 0b00010, 0b00100, 0b00010, 0b00001, 0b10001, 0b01110],
+        '4' => [0b00010, 0b00110, 0b01010, 0b10010, 0b11111, 0b00010, 0b00010],
+        '5' => [0b11111, 0b10000, 0b11110, 0b00001, 0b00001, 0b10001, 0b01110],
+        '6' => [0b00110, 0b01000, 0b10000, 0b11110, 0b10001, 0b10001, 0b01110],
+        '7' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b01000, 0b01000],
+        '8' => [0b01110, 0b10001, 0b10001, 0b01110, 0b10001, 0b10001, 0b01110],
+        '9' => [0b01110, 0b10001, 0b10001, 0b01111, 0b00001, 0b00010, 0b01100],
+        'A' => [0b01110, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'B' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10001, 0b10001, 0b11110],
+        'C' => [0b01110, 0b10001, 0b10000, 0b10000, 0b10000, 0b10001, 0b01110],
+        'D' => [0b11100, 0b10010, 0b10001, 0b10001, 0b10001, 0b10010, 0b11100],
+        'E' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b11111],
+        'F' => [0b11111, 0b10000, 0b10000, 0b11110, 0b10000, 0b10000, 0b10000],
+        'G' => [0b01110, 0b10001, 0b10000, 0b10111, 0b10001, 0b10001, 0b01111],
+        'H' => [0b10001, 0b10001, 0b10001, 0b11111, 0b10001, 0b10001, 0b10001],
+        'I' => [0b01110, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b01110],
+        'J' => [0b00111, 0b00010, 0b00010, 0b00010, 0b00010, 0b10010, 0b01100],
+        'K' => [0b10001, 0b10010, 0b10100, 0b11000, 0b10100, 0b10010, 0b10001],
+        'L' => [0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b10000, 0b11111],
+        'M' => [0b10001, 0b11011, 0b10101, 0b10101, 0b10001, 0b10001, 0b10001],
+        'N' => [0b10001, 0b10001, 0b11001, 0b10101, 0b10011, 0b10001, 0b10001],
+        'O' => [0b01110, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'P' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10000, 0b10000, 0b10000],
+        'Q' => [0b01110, 0b10001, 0b10001, 0b10001, 0b10101, 0b10010, 0b01101],
+        'R' => [0b11110, 0b10001, 0b10001, 0b11110, 0b10100, 0b10010, 0b10001],
+        'S' => [0b01111, 0b10000, 0b10000, 0b01110, 0b00001, 0b00001, 0b11110],
+        'T' => [0b11111, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100, 0b00100],
+        'U' => [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01110],
+        'V' => [0b10001, 0b10001, 0b10001, 0b10001, 0b10001, 0b01010, 0b00100],
+        'W' => [0b10001, 0b10001, 0b10001, 0b10101, 0b10101, 0b10101, 0b01010],
+        'X' => [0b10001, 0b10001, 0b01010, 0b00100, 0b01010, 0b10001, 0b10001],
+        'Y' => [0b10001, 0b10001, 0b10001, 0b01010, 0b00100, 0b00100, 0b00100],
+        'Z' => [0b11111, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b11111],
+        '.' => [0b00000, 0b00000, 0b00000, 0b00000, 0b00000, 0b01100, 0b01100],
+        '%' => [0b11000, 0b11001, 0b00010, 0b00100, 0b01000, 0b10011, 0b00011],
+        '+' => [0b00000, 0b00100, 0b00100, 0b11111, 0b00100, 0b00100, 0b00000],
+        '-' => [0b00000, 0b00000, 0b00000, 0b11111, 0b00000, 0b00000, 0b00000],
+        '/' => [0b00000, 0b00001, 0b00010, 0b00100, 0b01000, 0b10000, 0b00000],
+        _ => [0; 7],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compose() {
+        let paths = ["test_montage_1.png", "test_montage_2.png", "test_montage_3.png"];
+        for path in paths {
+            RgbaImage::from_pixel(40, 20, Rgba([0, 0, 255, 255])).save(path).unwrap();
+        }
+        let tiles = paths.iter()
+            .map(|path| MontageTile { image_path: path.to_string(), ticker: "AAPL".to_string(), change: Some("-1.5%".to_string()) })
+            .collect::<Vec<_>>();
+
+        let canvas = Montage::new(2).compose(&tiles).unwrap();
+        // cell: 40 + 2 * 4 wide, 20 + (7 * 2 + 2 * 4) + 4 high
+        assert_eq!((canvas.width(), canvas.height()), (96, 92));
+        // the charts are placed below the captions
+        assert_eq!(canvas.get_pixel(4 + 2, 22 + 2), &Rgba([0, 0, 255, 255]));
+        assert_eq!(canvas.get_pixel(48 + 4 + 2, 22 + 2), &Rgba([0, 0, 255, 255]));
+        assert_eq!(canvas.get_pixel(4 + 2, 46 + 22 + 2), &Rgba([0, 0, 255, 255]));
+        assert_ne!(canvas.get_pixel(4 + 2, 4 + 2), &Rgba([0, 0, 255, 255]));
+        assert_eq!(canvas.get_pixel(95, 91), &Montage::BACKGROUND);
+
+        for path in paths {
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_tiles_from_manifest() {
+        let table: TableData = vec![
+            vec!["Ticker".to_string(), "Change".to_string()],
+            vec!["AAPL".to_string(), "1.20%".to_string()],
+            vec!["MSFT".to_string(), "-0.50%".to_string()],
+        ];
+        let manifest = ChartManifest {
+            succeeded: vec![("MSFT".to_string(), "MSFT.png".to_string())],
+            failed: vec![("AAPL".to_string(), "error".to_string())],
+        };
+        let tiles = tiles_from_manifest(&manifest, &table);
+        assert_eq!(tiles.len(), 1);
+        assert_eq!(tiles[0].ticker, "MSFT");
+        assert_eq!(tiles[0].change.as_deref(), Some("-0.50%"));
+    }
+}
@@ -1,7 +1,16 @@
 use scraper::{Html, Selector};
 use crate::web_scraper::get_html_body;
-use crate::common::{TableData, Scrape};
+use crate::common::{Scrape, TableRow};
+use crate::output::{FeedItem, ToFeed};
 use async_trait::async_trait;
+use chrono::{DateTime, Datelike, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use futures::stream::{self, Stream};
+use regex::Regex;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::time::Duration;
+use strum::EnumIter;
 
 /// `News` struct provides a way to scrape News page and convert the content into `TableData`
 ///
@@ -20,20 +29,191 @@ use async_trait::async_trait;
 ///         .scrape().await?;
 ///     println!("{}", r.news.to_table(Some(News::default_header()), Some(5)));
 ///     println!("{}", r.blogs.to_table(Some(News::default_header()), Some(5)));
+///     if let Some(item) = r.news.first() {
+///         println!("{} {} ({})", item.published.to_rfc3339(), item.title, item.source);
+///     }
 ///     Ok(())
 /// }
 /// ```
 ///
 /// The above example demonstrates how to retrive both news and blogs of into two tables and print them.
-pub struct News {}
+///
+/// Finviz shows the time of today's items only, and the date of older items. The timestamps are
+/// resolved in the time zone of finviz, which is `America/New_York` by default and can be changed
+/// by `set_time_zone`.
+///
+/// Other tabs of the news page are selected by `set_view`, e.g. the news of the ETFs:
+///
+/// ```
+/// use finviz_rs::{
+///     news::{News, NewsView},
+///     output::ToTable,
+///     common::Scrape,
+/// };
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(),Box<dyn std::error::Error>>{
+///     let r = News::default()
+///         .set_view(NewsView::Etf)
+///         .scrape().await?;
+///     println!("{}", r.news.to_table(None, Some(5)));
+///     Ok(())
+/// }
+/// ```
+///
+/// New headlines are followed by `watch`, which can be narrowed down by the source and the title:
+///
+/// ```no_run
+/// use finviz_rs::news::News;
+/// use futures::StreamExt;
+/// use std::time::Duration;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(),Box<dyn std::error::Error>>{
+///     let mut news = News::default();
+///     news.add_source("reuters.com")
+///         .add_source("bloomberg.com")
+///         .add_keyword("Fed")
+///         .add_pattern(r"(?i)rate (hike|cut)s?")?;
+///
+///     let mut items = Box::pin(news.watch(Duration::from_secs(60)));
+///     while let Some(item) = items.next().await {
+///         match item {
+///             Ok(item) => println!("{} {} ({})", item.published, item.title, item.url),
+///             Err(e) => eprintln!("Failed to scrape news: {}", e),
+///         }
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct News {
+    time_zone: Tz,
+    view: NewsView,
+    sources: Vec<String>,
+    title_patterns: Vec<Regex>,
+}
+
+/// Represents the tabs of the news page used in `News`
+#[derive(Clone, Copy, Debug, Default, PartialEq, EnumIter)]
+pub enum NewsView {
+    /// market news and blogs
+    #[default]
+    Market,
+    /// stream of the news of all tickers
+    ByTicker,
+    /// news of the stocks
+    Stocks,
+    /// news of the ETFs
+    Etf,
+    /// news of the cryptocurrencies
+    Crypto,
+}
+
+impl NewsView {
+
+    // helper function to return the value of the `v` URL parameter
+    fn to_url_param(self) -> Option<&'static str> {
+        match self {
+            NewsView::Market => None,
+            NewsView::ByTicker => Some("2"),
+            NewsView::Stocks => Some("3"),
+            NewsView::Etf => Some("4"),
+            NewsView::Crypto => Some("5"),
+        }
+    }
+}
 
 /// a struct to store scraping data from `News`
 #[derive(Debug)]
 pub struct NewsData {
     /// data field to store scraping content of news
-    pub news: TableData,
+    pub news: Vec<NewsItem>,
     /// data field to store scraping content of blogs 
-    pub blogs: TableData,
+    pub blogs: Vec<NewsItem>,
+}
+
+/// Represents whether a `NewsItem` comes from the news or the blogs table
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NewsKind {
+    /// news of the media
+    News,
+    /// posts of the blogs
+    Blog,
+}
+
+impl fmt::Display for NewsKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NewsKind::News => write!(f, "News"),
+            NewsKind::Blog => write!(f, "Blog"),
+        }
+    }
+}
+
+/// A typed item of the news or blogs, returned in `NewsData`
+#[derive(Clone, Debug, PartialEq)]
+pub struct NewsItem {
+    /// time when the item was published, in the time zone of finviz. Items older than today are
+    /// shown with the date only, and resolved to the midnight of that date.
+    pub published: DateTime<Tz>,
+    /// title of the item with the HTML entities decoded
+    pub title: String,
+    /// domain of the source
+    pub source: String,
+    /// link of the item
+    pub url: String,
+    /// whether the item is news or a blog post
+    pub kind: NewsKind,
+    /// tickers labeled on the item, which is only available in the views other than `NewsView::Market`
+    pub tickers: Vec<String>,
+}
+
+impl TableRow for NewsItem {
+
+    fn header() -> Vec<String> {
+        News::default_header()
+    }
+
+    fn to_row(&self) -> Vec<String> {
+        vec![
+            self.published.format("%Y-%m-%d %H:%M").to_string(),
+            self.title.to_owned(),
+            self.source.to_owned(),
+            self.url.to_owned(),
+        ]
+    }
+}
+
+/// The item is categorized by its kind and tickers.
+impl From<&NewsItem> for FeedItem {
+    fn from(item: &NewsItem) -> Self {
+        let mut categories = vec![item.kind.to_string()];
+        categories.extend(item.tickers.iter().cloned());
+        FeedItem {
+            title: item.title.to_owned(),
+            link: item.url.to_owned(),
+            published: item.published.fixed_offset(),
+            source: item.source.to_owned(),
+            categories,
+        }
+    }
+}
+
+impl ToFeed for Vec<NewsItem> {
+
+    fn feed_items(&self) -> Vec<FeedItem> {
+        self.iter().map(FeedItem::from).collect()
+    }
+}
+
+/// The feed includes both news and blogs, from the latest.
+impl ToFeed for NewsData {
+
+    fn feed_items(&self) -> Vec<FeedItem> {
+        let mut items = self.news.iter().chain(self.blogs.iter()).collect::<Vec<_>>();
+        items.sort_by_key(|item| std::cmp::Reverse(item.published));
+        items.into_iter().map(FeedItem::from).collect()
+    }
 }
 
 impl Default for News {
@@ -49,7 +229,89 @@ impl News {
 
     /// Create new instance of `News`
     pub fn new() -> Self {
-        Self{}
+        Self{
+            time_zone: chrono_tz::America::New_York,
+            view: NewsView::default(),
+            sources: Vec::new(),
+            title_patterns: Vec::new(),
+        }
+    }
+
+    /// Only includes the items of the source domain, e.g. "wsj.com" includes "www.wsj.com" as well.
+    /// The items of any of the added sources are included.
+    pub fn add_source(&mut self, domain: &str) -> &mut Self {
+        self.sources.push(domain.trim().trim_start_matches("www.").to_lowercase());
+        self
+    }
+
+    /// Only includes the items with the keyword in the title, as a whole word ignoring the case, e.g.
+    /// "fed" does not match "FedEx". Keywords starting or ending with a symbol like "$AAPL" match the
+    /// symbol as it is. The items matching any of the added keywords or patterns are included.
+    pub fn add_keyword(&mut self, keyword: &str) -> &mut Self {
+        let keyword = keyword.trim();
+        // a word boundary is only next to a word character, so it is not added before "$"
+        let boundary = |c: Option<char>| if c.is_some_and(|c| c.is_alphanumeric() || c == '_') { r"\b" } else { "" };
+        let pattern = format!(
+            "(?i){}{}{}",
+            boundary(keyword.chars().next()),
+            regex::escape(keyword),
+            boundary(keyword.chars().last())
+        );
+        self.title_patterns.push(Regex::new(&pattern).expect("escaped keyword is a valid regex"));
+        self
+    }
+
+    /// Only includes the items with the title matching the regular expression.
+    /// The items matching any of the added keywords or patterns are included.
+    pub fn add_pattern(&mut self, pattern: &str) -> Result<&mut Self, regex::Error> {
+        self.title_patterns.push(Regex::new(pattern)?);
+        Ok(self)
+    }
+
+    /// Returns whether the item passes the source and title filters.
+    pub fn matches(&self, item: &NewsItem) -> bool {
+        let source = item.source.to_lowercase();
+        let source_matched = self.sources.is_empty() || self.sources.iter()
+            .any(|domain| source == *domain || source.ends_with(&format!(".{}", domain)));
+        let title_matched = self.title_patterns.is_empty() || self.title_patterns.iter()
+            .any(|pattern| pattern.is_match(&item.title));
+        source_matched && title_matched
+    }
+
+    /// Scrapes the news every interval, and yields the items not seen before from the oldest, starting
+    /// with the items on the page of the first scrape. The items are deduplicated by URL, and filtered
+    /// like `scrape`. An error of a scrape is yielded without ending the stream.
+    ///
+    /// The 5000 most recently seen URLs are remembered, so an item that drops off the page and comes
+    /// back later is not yielded again.
+    pub fn watch(&self, interval: Duration) -> impl Stream<Item = Result<NewsItem, Box<dyn std::error::Error>>> + '_ {
+        let state = (SeenUrls::new(SeenUrls::CAPACITY), VecDeque::new(), true);
+        stream::unfold(state, move |(mut seen, mut pending, mut first)| async move {
+            while pending.is_empty() {
+                if !first {
+                    tokio::time::sleep(interval).await;
+                }
+                first = false;
+                match self.scrape().await {
+                    Ok(data) => pending.extend(unseen_items(&mut seen, data).into_iter().map(Ok)),
+                    Err(e) => pending.push_back(Err(e)),
+                }
+            }
+            let item = pending.pop_front()?;
+            Some((item, (seen, pending, first)))
+        })
+    }
+
+    /// Sets the tab of the news page to scrape.
+    pub fn set_view(&mut self, view: NewsView) -> &mut Self {
+        self.view = view;
+        self
+    }
+
+    /// Sets the time zone the times shown on finviz are resolved in.
+    pub fn set_time_zone(&mut self, time_zone: Tz) -> &mut Self {
+        self.time_zone = time_zone;
+        self
     }
 
 
@@ -58,36 +320,103 @@ impl News {
         ["Time", "Title", "Source", "Link"].map(String::from).to_vec()
     }
 
+    /// Constructs the URL of the news page of the view.
+    fn get_url(&self) -> String {
+        match self.view.to_url_param() {
+            Some(view) => format!("{}?v={}", News::BASE_URL, view),
+            None => News::BASE_URL.to_string(),
+        }
+    }
+
 }
 
 #[async_trait]
 impl Scrape<NewsData> for News {
 
-    /// Scrapes the news data from the specified URL and return `TableData` on success, or `Box<dyn std::error::Error>` on failure
+    /// Scrapes the news data from the specified URL and return `NewsData` on success, or `Box<dyn std::error::Error>` on failure
     async fn scrape(&self) -> Result<NewsData, Box<dyn std::error::Error>> {
-        let body = get_html_body(News::BASE_URL).await?;
-        let document = Html::parse_document(&body);
+        let body = get_html_body(&self.get_url()).await?;
+        let now = Utc::now().with_timezone(&self.time_zone);
+        let mut data = match self.view {
+            NewsView::Market => parse_news_page(&body, now)?,
+            _ => parse_news_stream(&body, now)?,
+        };
+        data.news.retain(|item| self.matches(item));
+        data.blogs.retain(|item| self.matches(item));
+        Ok(data)
+    }
+}
 
-        let news_content_selector = Selector::parse("#news table").map_err(|err| err.to_string())?; 
-        let news_content = document.select(&news_content_selector).next().ok_or("Cannot find News content ".to_string())?;
+// The URLs seen by `News::watch`, bounded to the most recently seen ones
+struct SeenUrls {
+    capacity: usize,
+    scrapes: u64,
+    last_seen: HashMap<String, u64>,
+}
 
-        let tables_selector = Selector::parse("table").map_err(|err| err.to_string())?;
-        let mut tables = news_content.select(&tables_selector);
+impl SeenUrls {
+    const CAPACITY: usize = 5000;
 
-        let news = tables.next().ok_or("Cannot find News table".to_string())?;
-        let blog = tables.next().ok_or("Cannot find Blog table".to_string())?;
+    fn new(capacity: usize) -> Self {
+        Self { capacity, scrapes: 0, last_seen: HashMap::new() }
+    }
 
-        let news = parse_news_table(news)?;
-        let blogs = parse_news_table(blog)?;
-        //let blogs = news.clone();
+    // helper function to remember the URLs of a scrape, and forget the least recently seen ones
+    // beyond the capacity
+    fn insert_all<'a>(&mut self, urls: impl Iterator<Item = &'a String>) {
+        self.scrapes += 1;
+        for url in urls {
+            self.last_seen.insert(url.to_owned(), self.scrapes);
+        }
 
-        Ok( NewsData { news, blogs} )
+        if self.last_seen.len() > self.capacity {
+            let mut by_age = self.last_seen.iter().map(|(url, scrape)| (*scrape, url.to_owned())).collect::<Vec<_>>();
+            by_age.sort();
+            for (_, url) in by_age.into_iter().take(self.last_seen.len() - self.capacity) {
+                self.last_seen.remove(&url);
+            }
+        }
     }
 }
 
+// helper function to return the items with the URLs not in `seen` ordered from the oldest, and
+// remember the URLs of the items
+fn unseen_items(seen: &mut SeenUrls, data: NewsData) -> Vec<NewsItem> {
+    let items = data.news.into_iter().chain(data.blogs).collect::<Vec<_>>();
+
+    let mut emitted = HashSet::new();
+    let mut unseen = items.iter()
+        .filter(|item| !seen.last_seen.contains_key(&item.url) && emitted.insert(item.url.to_owned()))
+        .cloned()
+        .collect::<Vec<_>>();
+    unseen.sort_by_key(|item| item.published);
+
+    seen.insert_all(items.iter().map(|item| &item.url));
+    unseen
+}
+
+// helper function to parse the news page relative to the current time in the time zone of finviz
+fn parse_news_page(body: &str, now: DateTime<Tz>) -> Result<NewsData, Box<dyn std::error::Error>> {
+    let document = Html::parse_document(body);
+
+    let news_content_selector = Selector::parse("#news table").map_err(|err| err.to_string())?; 
+    let news_content = document.select(&news_content_selector).next().ok_or("Cannot find News content ".to_string())?;
+
+    let tables_selector = Selector::parse("table").map_err(|err| err.to_string())?;
+    let mut tables = news_content.select(&tables_selector);
+
+    let news = tables.next().ok_or("Cannot find News table".to_string())?;
+    let blog = tables.next().ok_or("Cannot find Blog table".to_string())?;
+
+    let news = parse_news_table(news, NewsKind::News, now)?;
+    let blogs = parse_news_table(blog, NewsKind::Blog, now)?;
+
+    Ok( NewsData { news, blogs} )
+}
 
-// helper function to parse news data and return `TableDat` on success, or `Box<dyn std::error::Error>` on failure
-fn parse_news_table(table: scraper::element_ref::ElementRef<'_>) -> Result<TableData, Box<dyn std::error::Error>> {
+
+// helper function to parse news data and return `NewsItem`s on success, or `Box<dyn std::error::Error>` on failure
+fn parse_news_table(table: scraper::element_ref::ElementRef<'_>, kind: NewsKind, now: DateTime<Tz>) -> Result<Vec<NewsItem>, Box<dyn std::error::Error>> {
     let row_selector = Selector::parse("tr")?;
     let rows = table.select(&row_selector);
     let mut data = Vec::new();
@@ -101,17 +430,11 @@ fn parse_news_table(table: scraper::element_ref::ElementRef<'_>) -> Result<Table
         if let (Some(_), Some(date), Some(tag_a)) = (cols.next(), cols.next(), cols.next()) {
             let link_selector = Selector::parse("a")?;
             if let Some(link) = tag_a.select(&link_selector).next() {
-                let date_text = date.inner_html();
-                let title_text = link.inner_html();
-                let link_href = link.value().attr("href").ok_or("N/A").unwrap().to_string();
-
-                let source = if link_href.contains("feedproxy.google.com") {
-                    link_href.split('/').nth(4).ok_or("Source not found".to_string())?
-                } else {
-                    link_href.split('/').nth(2).ok_or("Source not found".to_string())?
-                };
-                let row_data = vec![date_text, title_text, source.to_string(), link_href];
-                data.push(row_data);
+                let date_text = date.text().collect::<String>();
+                match parse_news_item(&date_text, link, kind, Vec::new(), now) {
+                    Ok(item) => data.push(item),
+                    Err(e) => println!("Fail to parse the row: {}", e),
+                }
             } else {
                 println!("Fail to parse tag a");
             }
@@ -124,6 +447,125 @@ fn parse_news_table(table: scraper::element_ref::ElementRef<'_>) -> Result<Table
     Ok(data)
 }
 
+// helper function to parse the single table of the views other than `NewsView::Market`, which has
+// no blogs, and labels the tickers of each item
+fn parse_news_stream(body: &str, now: DateTime<Tz>) -> Result<NewsData, Box<dyn std::error::Error>> {
+    let document = Html::parse_document(body);
+
+    let row_selector = Selector::parse("tr.news_table-row")?;
+    let col_selector = Selector::parse("td")?;
+    let link_selector = Selector::parse("a")?;
+    let title_selector = Selector::parse("a.nn-tab-link")?;
+
+    let mut news = Vec::new();
+    for row in document.select(&row_selector) {
+        let Some(date) = row.select(&col_selector).next() else {
+            println!("Fail to parse the row");
+            continue;
+        };
+        let date_text = date.text().collect::<String>();
+
+        let tickers = row.select(&link_selector)
+            .filter_map(|a| a.value().attr("href").and_then(ticker_of_link))
+            .fold(Vec::new(), |mut tickers, ticker| {
+                if !tickers.contains(&ticker) {
+                    tickers.push(ticker);
+                }
+                tickers
+            });
+
+        let link = row.select(&title_selector).next()
+            .or_else(|| row.select(&link_selector).find(|a| a.value().attr("href").and_then(ticker_of_link).is_none()));
+        let Some(link) = link else {
+            println!("Fail to parse tag a");
+            continue;
+        };
+
+        match parse_news_item(&date_text, link, NewsKind::News, tickers, now) {
+            Ok(item) => news.push(item),
+            Err(e) => println!("Fail to parse the row: {}", e),
+        }
+    }
+
+    if news.is_empty() {
+        return Err("Cannot find News table".into());
+    }
+    Ok( NewsData { news, blogs: Vec::new() } )
+}
+
+// helper function to convert the time and the link of a row to `NewsItem`
+fn parse_news_item(date_text: &str, link: scraper::element_ref::ElementRef<'_>, kind: NewsKind, tickers: Vec<String>, now: DateTime<Tz>) -> Result<NewsItem, Box<dyn std::error::Error>> {
+    let title_text = link.text().collect::<String>().trim().to_string();
+    let link_href = link.value().attr("href").ok_or("Link not found".to_string())?;
+    let link_href = if link_href.starts_with('/') && !link_href.starts_with("//") {
+        format!("https://finviz.com{}", link_href)
+    } else {
+        link_href.to_string()
+    };
+
+    let source = if link_href.contains("feedproxy.google.com") {
+        link_href.split('/').nth(4).ok_or("Source not found".to_string())?
+    } else {
+        link_href.split('/').nth(2).ok_or("Source not found".to_string())?
+    };
+    let published = parse_news_time(date_text, now)
+        .ok_or(format!("Invalid time of news: {}", date_text.trim()))?;
+
+    Ok(NewsItem {
+        published,
+        title: title_text,
+        source: source.to_string(),
+        url: link_href.to_owned(),
+        kind,
+        tickers,
+    })
+}
+
+// helper function to return the ticker of a link to the quote page, e.g. "/quote.ashx?t=AAPL&p=d"
+fn ticker_of_link(href: &str) -> Option<String> {
+    let (_, query) = href.split_once("quote.ashx?")?;
+    query.split('&')
+        .find_map(|param| param.strip_prefix("t="))
+        .filter(|ticker| !ticker.is_empty())
+        .map(|ticker| ticker.to_uppercase())
+}
+
+// helper function to resolve the time shown on finviz, e.g. "09:41AM" for today, "Jun-02" for older
+// items, or both like "Jun-02-23 09:41AM" and "Today 09:41AM", in the time zone of `now`. A time
+// without date after `now` is of yesterday, e.g. around midnight or with a clock skew.
+fn parse_news_time(text: &str, now: DateTime<Tz>) -> Option<DateTime<Tz>> {
+    let text = text.trim();
+    let today = now.date_naive();
+
+    let naive = match text.split_once(char::is_whitespace) {
+        Some((date, time)) => parse_news_date(date, today)?.and_time(NaiveTime::parse_from_str(time.trim(), "%I:%M%p").ok()?),
+        None => match NaiveTime::parse_from_str(text, "%I:%M%p") {
+            Ok(time) if today.and_time(time) > now.naive_local() => today.pred_opt()?.and_time(time),
+            Ok(time) => today.and_time(time),
+            Err(_) => parse_news_date(text, today)?.and_time(NaiveTime::MIN),
+        },
+    };
+
+    now.timezone().from_local_datetime(&naive).earliest()
+}
+
+// helper function to resolve the date shown on finviz, e.g. "Today", "Jun-02-23" or "Jun-02"
+fn parse_news_date(text: &str, today: NaiveDate) -> Option<NaiveDate> {
+    if text.eq_ignore_ascii_case("today") {
+        return Some(today);
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(text, "%b-%d-%y") {
+        return Some(date);
+    }
+
+    // the year is omitted, which is the latest year that does not put the date in the future; parsed
+    // in a leap year first, so "Feb-29" is accepted and resolved as the latest leap year
+    let date = NaiveDate::parse_from_str(&format!("{}-2000", text), "%b-%d-%Y").ok()?;
+    (today.year() - 8..=today.year()).rev()
+        .filter_map(|year| date.with_year(year))
+        .find(|date| *date <= today)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -138,4 +580,172 @@ mod tests {
         assert!(!r.news.is_empty(), "Empty news in the response");
         assert!(!r.blogs.is_empty(), "Empty blogs in the response");
     }
+
+    fn now() -> DateTime<Tz> {
+        chrono_tz::America::New_York.with_ymd_and_hms(2023, 5, 15, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_parse_news_time() {
+        let ny = chrono_tz::America::New_York;
+        assert_eq!(parse_news_time("09:41AM", now()), ny.with_ymd_and_hms(2023, 5, 15, 9, 41, 0).single());
+        assert_eq!(parse_news_time(" 10:05PM ", now()), ny.with_ymd_and_hms(2023, 5, 14, 22, 5, 0).single());
+        assert_eq!(parse_news_time("12:00PM", now()), ny.with_ymd_and_hms(2023, 5, 15, 12, 0, 0).single());
+        assert_eq!(parse_news_time("May-14", now()), ny.with_ymd_and_hms(2023, 5, 14, 0, 0, 0).single());
+        assert_eq!(parse_news_time("Jun-02", now()), ny.with_ymd_and_hms(2022, 6, 2, 0, 0, 0).single());
+        assert_eq!(parse_news_time("Jun-02-23 09:41AM", now()), ny.with_ymd_and_hms(2023, 6, 2, 9, 41, 0).single());
+        assert_eq!(parse_news_time("Jun-02 09:41AM", now()), ny.with_ymd_and_hms(2022, 6, 2, 9, 41, 0).single());
+        assert_eq!(parse_news_time("Today 09:41AM", now()), ny.with_ymd_and_hms(2023, 5, 15, 9, 41, 0).single());
+        assert_eq!(parse_news_time("n/a", now()), None);
+
+        let today = NaiveDate::from_ymd_opt(2025, 3, 1).unwrap();
+        assert_eq!(parse_news_date("Feb-29", today), NaiveDate::from_ymd_opt(2024, 2, 29));
+        assert_eq!(parse_news_date("Feb-28", today), NaiveDate::from_ymd_opt(2025, 2, 28));
+        assert_eq!(parse_news_date("Feb-30", today), None);
+    }
+
+    fn item(title: &str, source: &str, url: &str, hour: u32) -> NewsItem {
+        NewsItem {
+            published: chrono_tz::America::New_York.with_ymd_and_hms(2023, 5, 15, hour, 0, 0).unwrap(),
+            title: title.to_string(),
+            source: source.to_string(),
+            url: url.to_string(),
+            kind: NewsKind::News,
+            tickers: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_matches() {
+        let mut news = News::default();
+        assert!(news.matches(&item("Anything", "www.wsj.com", "a", 9)));
+
+        news.add_source("www.wsj.com").add_source("Reuters.com");
+        assert!(news.matches(&item("Anything", "www.wsj.com", "a", 9)));
+        assert!(news.matches(&item("Anything", "reuters.com", "a", 9)));
+        assert!(!news.matches(&item("Anything", "notwsj.com", "a", 9)));
+
+        news.add_keyword("fed").add_pattern(r"rate (hike|cut)s?").unwrap();
+        assert!(news.matches(&item("The Fed holds", "wsj.com", "a", 9)));
+        assert!(news.matches(&item("Markets price rate cuts", "wsj.com", "a", 9)));
+        assert!(!news.matches(&item("FedEx rallies", "wsj.com", "a", 9)));
+        assert!(!news.matches(&item("The Fed holds", "cnbc.com", "a", 9)));
+        assert!(news.add_pattern("(").is_err());
+
+        let mut news = News::default();
+        news.add_keyword("$AAPL").add_keyword("S&P 500");
+        assert!(news.matches(&item("Why $aapl rallied", "wsj.com", "a", 9)));
+        assert!(news.matches(&item("S&P 500 hits a record", "wsj.com", "a", 9)));
+        assert!(!news.matches(&item("$AAPLX is not Apple", "wsj.com", "a", 9)));
+        assert!(!news.matches(&item("S&P 5000", "wsj.com", "a", 9)));
+    }
+
+    #[test]
+    fn test_unseen_items() {
+        let mut seen = SeenUrls::new(3);
+        let data = NewsData {
+            news: vec![item("B", "wsj.com", "b", 10), item("A", "wsj.com", "a", 9)],
+            blogs: vec![item("A again", "wsj.com", "a", 9)],
+        };
+        let titles = |items: Vec<NewsItem>| items.into_iter().map(|i| i.title).collect::<Vec<_>>();
+        assert_eq!(titles(unseen_items(&mut seen, data)), vec!["A", "B"]);
+
+        let data = NewsData { news: vec![item("C", "wsj.com", "c", 11), item("B", "wsj.com", "b", 10)], blogs: Vec::new() };
+        assert_eq!(titles(unseen_items(&mut seen, data)), vec!["C"]);
+
+        // an item dropped from the page and back is not emitted again
+        let data = NewsData { news: vec![item("C", "wsj.com", "c", 11), item("A", "wsj.com", "a", 9)], blogs: Vec::new() };
+        assert!(unseen_items(&mut seen, data).is_empty());
+
+        // the least recently seen URL is forgotten beyond the capacity
+        let data = NewsData { news: vec![item("D", "wsj.com", "d", 12), item("A", "wsj.com", "a", 9)], blogs: Vec::new() };
+        assert_eq!(titles(unseen_items(&mut seen, data)), vec!["D"]);
+        assert_eq!(seen.last_seen.len(), 3);
+        assert!(!seen.last_seen.contains_key("b"));
+    }
+
+    #[test]
+    fn test_feed_items() {
+        let mut later = item("Later", "blog.example.com", "https://blog.example.com/b", 10);
+        later.kind = NewsKind::Blog;
+        let mut earlier = item("Earlier", "wsj.com", "https://www.wsj.com/a", 9);
+        earlier.tickers = vec!["AAPL".to_string()];
+        let data = NewsData { news: vec![earlier], blogs: vec![later] };
+
+        let items = data.feed_items();
+        assert_eq!(items.iter().map(|i| i.title.as_str()).collect::<Vec<_>>(), vec!["Later", "Earlier"]);
+        assert_eq!(items[1].categories, vec!["News", "AAPL"]);
+        assert_eq!(items[1].published.to_rfc3339(), "2023-05-15T09:00:00-04:00");
+        assert!(data.to_rss(&crate::output::FeedChannel::default()).contains("<category>Blog</category>"));
+    }
+
+    #[test]
+    fn test_url() {
+        let mut news = News::default();
+        assert_eq!(news.get_url(), "https://finviz.com/news.ashx");
+        assert_eq!(news.set_view(NewsView::Etf).get_url(), "https://finviz.com/news.ashx?v=4");
+        assert_eq!(news.set_view(NewsView::ByTicker).get_url(), "https://finviz.com/news.ashx?v=2");
+    }
+
+    #[test]
+    fn test_parse_news_stream() {
+        let body = r#"
+            <table class="styled-table-new">
+                <tr class="news_table-row">
+                    <td class="news_date-cell">Today 09:41AM</td>
+                    <td><a class="nn-tab-link" href="/news/123/apple-and-microsoft">Apple &amp; Microsoft Rise</a>
+                        <div class="news-badges-container">
+                            <a class="stock-news-label" href="/quote.ashx?t=AAPL&p=d"><span>AAPL</span></a>
+                            <a class="stock-news-label" href="/quote.ashx?t=MSFT"><span>MSFT</span></a>
+                        </div>
+                    </td>
+                </tr>
+                <tr class="news_table-row">
+                    <td>Someday</td>
+                    <td><a class="nn-tab-link" href="https://www.wsj.com/articles/b">Unknown Time</a></td>
+                </tr>
+                <tr class="news_table-row"><td></td></tr>
+                <tr class="news_table-row">
+                    <td>May-12 10:00PM</td>
+                    <td><a href="/quote.ashx?t=spy">SPY</a><a href="https://www.reuters.com/markets/etf">ETF Flows</a></td>
+                </tr>
+            </table>"#;
+        let data = parse_news_stream(body, now()).unwrap();
+        assert!(data.blogs.is_empty());
+        assert_eq!(data.news.len(), 2);
+        assert_eq!(data.news[0].title, "Apple & Microsoft Rise");
+        assert_eq!(data.news[0].url, "https://finviz.com/news/123/apple-and-microsoft");
+        assert_eq!(data.news[0].source, "finviz.com");
+        assert_eq!(data.news[0].tickers, vec!["AAPL", "MSFT"]);
+        assert_eq!(data.news[1].title, "ETF Flows");
+        assert_eq!(data.news[1].tickers, vec!["SPY"]);
+        assert_eq!(data.news[1].to_row()[0], "2023-05-12 22:00");
+        assert!(parse_news_stream("<table></table>", now()).is_err());
+    }
+
+    #[test]
+    fn test_parse_news_page() {
+        let body = r#"
+            <div id="news"><table><tr><td>
+                <table>
+                    <tr><td></td><td>09:41AM</td><td><a href="https://www.wsj.com/articles/a">Stocks &amp; Bonds Rally</a></td></tr>
+                    <tr><td></td><td>May-12</td><td><a href="http://feedproxy.google.com/~r/source/~3/b">Older</a></td></tr>
+                    <tr><td></td><td>n/a</td><td><a href="https://www.wsj.com/articles/c">Unknown Time</a></td></tr>
+                </table>
+                <table>
+                    <tr><td></td><td>08:00AM</td><td><a href="https://blog.example.com/post">Q&amp;A</a></td></tr>
+                </table>
+            </td></tr></table></div>"#;
+        let data = parse_news_page(body, now()).unwrap();
+        assert_eq!(data.news.len(), 2);
+        assert_eq!(data.news[0].title, "Stocks & Bonds Rally");
+        assert_eq!(data.news[0].source, "www.wsj.com");
+        assert_eq!(data.news[0].kind, NewsKind::News);
+        assert_eq!(data.news[0].to_row()[0], "2023-05-15 09:41");
+        assert_eq!(data.news[1].source, "source");
+        assert_eq!(data.news[1].published.date_naive(), NaiveDate::from_ymd_opt(2023, 5, 12).unwrap());
+        assert_eq!(data.blogs.len(), 1);
+        assert_eq!(data.blogs[0].title, "Q&A");
+        assert_eq!(data.blogs[0].kind, NewsKind::Blog);
+    }
 }
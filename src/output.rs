@@ -1,9 +1,11 @@
 use tabled::settings::{Style, Width, Modify, object::Rows};
-use crate::common::{DictData, TableData};
+use crate::common::{DictData, TableData, TableRow};
 use csv::Writer;
 use std::error::Error;
 use std::fs::File;
 use serde_json::{Value, json};
+use crate::web_scraper::write_file_atomic;
+use chrono::{DateTime, FixedOffset, Utc};
 
 /// A trait for converting data into a formatted table.
 pub trait ToTable {
@@ -43,6 +45,20 @@ impl ToTable for TableData {
     }
 }
 
+/// Implements the `ToTable` trait for a list of typed records, using `TableRow::header` when no
+/// headers are given.
+impl<T: TableRow> ToTable for Vec<T> {
+
+    fn to_table(&self, headers: Option<Vec<String>>, max_rows: Option<usize>) -> String {
+        to_table_data(self).to_table(headers.or_else(|| Some(T::header())), max_rows)
+    }
+}
+
+/// Converts a list of typed records into `TableData` without the header row.
+pub fn to_table_data<T: TableRow>(records: &[T]) -> TableData {
+    records.iter().map(TableRow::to_row).collect()
+}
+
 /// Converts a dictionary into a table representation.
 ///
 /// This function takes a dictionary (`dict`) and converts it into a table representation,
@@ -147,6 +163,15 @@ impl ToCsvFile for TableData {
     }
 }
 
+#[cfg(feature = "output_csv")]
+impl<T: TableRow> ToCsvFile for Vec<T> {
+    fn to_csv_file(&self, file_path: &str) -> Result<(), Box<dyn Error>> {
+        let mut data = vec![T::header()];
+        data.extend(to_table_data(self));
+        data.to_csv_file(file_path)
+    }
+}
+
 /// Convert data to a json data
 pub trait ToJson {
 
@@ -174,6 +199,187 @@ impl ToJson for TableData {
     }
 }
 
+impl<T: TableRow> ToJson for Vec<T> {
+
+    fn to_json(&self, header: Option<Vec<String>>) -> Result<Value, Box<dyn Error>> {
+        to_table_data(self).to_json(header.or_else(|| Some(T::header())))
+    }
+}
+
+/// The channel of a news feed written by `ToFeed`
+#[derive(Clone, Debug)]
+pub struct FeedChannel {
+    title: String,
+    link: String,
+    description: String,
+}
+
+impl Default for FeedChannel {
+
+    /// Create the channel of the finviz news
+    fn default() -> Self {
+        FeedChannel::new("Finviz News", "https://finviz.com/news.ashx")
+    }
+}
+
+impl FeedChannel {
+
+    /// Create a channel with the title and the link of the website.
+    pub fn new(title: &str, link: &str) -> Self {
+        Self { title: title.to_string(), link: link.to_string(), description: title.to_string() }
+    }
+
+    /// Sets the description, which is the title by default.
+    pub fn set_description(&mut self, description: &str) -> &mut Self {
+        self.description = description.to_string();
+        self
+    }
+}
+
+/// An item of a feed written by `ToFeed`
+#[derive(Clone, Debug, PartialEq)]
+pub struct FeedItem {
+    /// title of the item
+    pub title: String,
+    /// link of the item, from which the GUID is derived
+    pub link: String,
+    /// time when the item was published
+    pub published: DateTime<FixedOffset>,
+    /// name of the source, e.g. "www.wsj.com"
+    pub source: String,
+    /// categories of the item, e.g. the kind and the tickers of news
+    pub categories: Vec<String>,
+}
+
+/// Convert items like news to RSS 2.0 or Atom feeds
+///
+/// # Example
+///
+/// ```
+/// use finviz_rs::{
+///     news::News,
+///     output::{FeedChannel, ToFeed},
+///     common::Scrape,
+/// };
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(),Box<dyn std::error::Error>>{
+///     let news = News::default().scrape().await?;
+///     news.to_rss_file("news.xml", &FeedChannel::default())?;
+///     news.to_atom_file("news.atom", &FeedChannel::default())?;
+///     Ok(())
+/// }
+/// ```
+///
+/// The GUIDs of the items are derived from the links, so the same item keeps its GUID across scrapes.
+pub trait ToFeed {
+
+    /// Returns the items of the feed, in the order written.
+    fn feed_items(&self) -> Vec<FeedItem>;
+
+    /// Converts the items to an RSS 2.0 document.
+    fn to_rss(&self, channel: &FeedChannel) -> String {
+        let items = self.feed_items();
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<rss version=\"2.0\">\n<channel>\n");
+        xml += &format!("<title>{}</title>\n", escape_xml(&channel.title));
+        xml += &format!("<link>{}</link>\n", escape_xml(&channel.link));
+        xml += &format!("<description>{}</description>\n", escape_xml(&channel.description));
+        if let Some(latest) = items.iter().map(|item| item.published).max() {
+            xml += &format!("<lastBuildDate>{}</lastBuildDate>\n", latest.to_rfc2822());
+        }
+
+        for item in items.iter() {
+            xml += "<item>\n";
+            xml += &format!("<title>{}</title>\n", escape_xml(&item.title));
+            xml += &format!("<link>{}</link>\n", escape_xml(&item.link));
+            xml += &format!("<guid isPermaLink=\"false\">{}</guid>\n", feed_guid(&item.link));
+            xml += &format!("<pubDate>{}</pubDate>\n", item.published.to_rfc2822());
+            // the url of the RSS source is the feed the item came from, which is the channel
+            xml += &format!("<source url=\"{}\">{}</source>\n", escape_xml(&channel.link), escape_xml(&item.source));
+            for category in item.categories.iter() {
+                xml += &format!("<category>{}</category>\n", escape_xml(category));
+            }
+            xml += "</item>\n";
+        }
+
+        xml += "</channel>\n</rss>\n";
+        xml
+    }
+
+    /// Converts the items to an Atom document.
+    fn to_atom(&self, channel: &FeedChannel) -> String {
+        let items = self.feed_items();
+        let updated = items.iter().map(|item| item.published.with_timezone(&Utc)).max().unwrap_or_else(Utc::now);
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+        xml += &format!("<id>{}</id>\n", feed_guid(&channel.link));
+        xml += &format!("<title>{}</title>\n", escape_xml(&channel.title));
+        xml += &format!("<subtitle>{}</subtitle>\n", escape_xml(&channel.description));
+        xml += &format!("<link href=\"{}\"/>\n", escape_xml(&channel.link));
+        xml += &format!("<updated>{}</updated>\n", updated.to_rfc3339());
+
+        for item in items.iter() {
+            xml += "<entry>\n";
+            xml += &format!("<id>{}</id>\n", feed_guid(&item.link));
+            xml += &format!("<title>{}</title>\n", escape_xml(&item.title));
+            xml += &format!("<link href=\"{}\"/>\n", escape_xml(&item.link));
+            xml += &format!("<updated>{}</updated>\n", item.published.to_rfc3339());
+            xml += &format!("<author><name>{}</name></author>\n", escape_xml(&item.source));
+            for category in item.categories.iter() {
+                xml += &format!("<category term=\"{}\"/>\n", escape_xml(category));
+            }
+            xml += "</entry>\n";
+        }
+
+        xml += "</feed>\n";
+        xml
+    }
+
+    /// Writes the RSS 2.0 document to the file.
+    fn to_rss_file(&self, file_path: &str, channel: &FeedChannel) -> Result<(), Box<dyn Error>> {
+        write_file_atomic(file_path, self.to_rss(channel).as_bytes())?;
+        Ok(())
+    }
+
+    /// Writes the Atom document to the file.
+    fn to_atom_file(&self, file_path: &str, channel: &FeedChannel) -> Result<(), Box<dyn Error>> {
+        write_file_atomic(file_path, self.to_atom(channel).as_bytes())?;
+        Ok(())
+    }
+}
+
+impl ToFeed for Vec<FeedItem> {
+
+    fn feed_items(&self) -> Vec<FeedItem> {
+        self.clone()
+    }
+}
+
+// helper function to escape the special characters of XML
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // control characters are not allowed in XML 1.0
+            c if c.is_control() && !matches!(c, '\n' | '\r' | '\t') => {},
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// helper function to derive a stable GUID from the link, by the 64-bit FNV-1a hash
+fn feed_guid(link: &str) -> String {
+    let hash = link.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("urn:finviz-rs:{:016x}", hash)
+}
 
 #[cfg(test)]
 mod tests {
@@ -256,4 +462,52 @@ mod tests {
         assert!(&json_data.is_ok());
 
     }
+
+    fn feed_item(title: &str, link: &str, hour: u32) -> FeedItem {
+        use chrono::TimeZone;
+        FeedItem {
+            title: title.to_string(),
+            link: link.to_string(),
+            published: FixedOffset::west_opt(4 * 3600).unwrap().with_ymd_and_hms(2023, 5, 15, hour, 30, 0).unwrap(),
+            source: "www.wsj.com".to_string(),
+            categories: vec!["News".to_string(), "AAPL".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_to_rss() {
+        let items = vec![
+            feed_item("Later", "https://blog.example.com/b", 10),
+            feed_item("Stocks & <Bonds>", "https://www.wsj.com/a?x=1&y=2", 9),
+        ];
+        let rss = items.to_rss(&FeedChannel::default());
+        assert!(rss.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<rss version=\"2.0\">"));
+        assert!(rss.contains("<title>Stocks &amp; &lt;Bonds&gt;</title>"));
+        assert!(rss.contains("<link>https://www.wsj.com/a?x=1&amp;y=2</link>"));
+        assert!(rss.contains("<pubDate>Mon, 15 May 2023 09:30:00 -0400</pubDate>"));
+        assert!(rss.contains("<lastBuildDate>Mon, 15 May 2023 10:30:00 -0400</lastBuildDate>"));
+        assert!(rss.contains("<source url=\"https://finviz.com/news.ashx\">www.wsj.com</source>"));
+        assert!(rss.contains("<category>AAPL</category>"));
+        assert!(rss.find("<title>Later</title>") < rss.find("<title>Stocks"));
+        assert_eq!(rss.matches("<item>").count(), 2);
+    }
+
+    #[test]
+    fn test_to_atom() {
+        let items = vec![feed_item("It's \"quoted\"", "https://www.wsj.com/a", 9)];
+        let atom = items.to_atom(&FeedChannel::new("Desk", "https://example.com/"));
+        assert!(atom.contains("<feed xmlns=\"http://www.w3.org/2005/Atom\">"));
+        assert!(atom.contains("<title>It&apos;s &quot;quoted&quot;</title>"));
+        assert!(atom.contains("<updated>2023-05-15T09:30:00-04:00</updated>"));
+        assert!(atom.contains("<updated>2023-05-15T13:30:00+00:00</updated>"));
+        assert!(atom.contains("<category term=\"News\"/>"));
+        assert!(atom.contains(&format!("<id>{}</id>", feed_guid("https://www.wsj.com/a"))));
+    }
+
+    #[test]
+    fn test_feed_guid() {
+        assert_eq!(feed_guid(""), "urn:finviz-rs:cbf29ce484222325");
+        assert_eq!(feed_guid("a"), "urn:finviz-rs:af63dc4c8601ec8c");
+        assert_ne!(feed_guid("https://www.wsj.com/a"), feed_guid("https://www.wsj.com/b"));
+    }
 }
@@ -0,0 +1,60 @@
+
+/// Parses a number as displayed on finviz into `f64`.
+///
+/// Thousands separators and a trailing `%` are removed, and the abbreviations `K`, `M`, `B` and `T`
+/// are expanded. Finviz uses `-` for missing values, which is returned as `None`.
+///
+/// # Example
+///
+/// ```
+/// use finviz_rs::parse::parse_number;
+///
+/// assert_eq!(parse_number("1,234.5"), Some(1234.5));
+/// assert_eq!(parse_number("-3.25%"), Some(-3.25));
+/// assert_eq!(parse_number("2.5B"), Some(2_500_000_000.0));
+/// assert_eq!(parse_number("-"), None);
+/// ```
+pub fn parse_number(text: &str) -> Option<f64> {
+    let text = text.trim().trim_end_matches('%').replace(',', "");
+    if text.is_empty() || text == "-" {
+        return None;
+    }
+
+    let (number, multiplier) = match text.chars().last() {
+        Some('K') => (&text[..text.len() - 1], 1e3),
+        Some('M') => (&text[..text.len() - 1], 1e6),
+        Some('B') => (&text[..text.len() - 1], 1e9),
+        Some('T') => (&text[..text.len() - 1], 1e12),
+        _ => (text.as_str(), 1.0),
+    };
+
+    number.parse::<f64>().ok().map(|n| n * multiplier)
+}
+
+/// Parses an integer count as displayed on finviz (e.g. `"1,342,656"`) into `u64`.
+///
+/// Returns `None` if the text is empty, `-` or not an integer.
+pub fn parse_count(text: &str) -> Option<u64> {
+    text.trim().replace(',', "").parse::<u64>().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_number() {
+        assert_eq!(parse_number("172.50"), Some(172.5));
+        assert_eq!(parse_number(" 12.13% "), Some(12.13));
+        assert_eq!(parse_number("84.14K"), Some(84_140.0));
+        assert_eq!(parse_number("25.21M"), Some(25_210_000.0));
+        assert_eq!(parse_number(""), None);
+        assert_eq!(parse_number("N/A"), None);
+    }
+
+    #[test]
+    fn test_parse_count() {
+        assert_eq!(parse_count("1,342,656"), Some(1_342_656));
+        assert_eq!(parse_count("-"), None);
+    }
+}
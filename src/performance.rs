@@ -0,0 +1,203 @@
+use crate::common::{TableData, TableRow};
+use crate::parse::parse_number;
+use std::fmt;
+use strum::EnumIter;
+
+/// Represents the unit of the performance in `PerformanceRow`
+#[derive(Clone, Copy, Debug, PartialEq, EnumIter)]
+pub enum PerformanceUnit {
+    /// change in percent, e.g. 1.5 for "1.50%"
+    Percent,
+    /// change in PIPS, the smallest price move of a forex pair
+    Pips,
+}
+
+/// Represents the sort orders of the performance tables of `Forex` and `Crypto`
+#[derive(Clone, Copy, Debug, PartialEq, EnumIter)]
+pub enum PerformanceOrder {
+    /// order by ticker
+    Ticker,
+    /// order by price
+    Price,
+    /// order by the performance of 5 minutes
+    Perf5Min,
+    /// order by the performance of the hour
+    PerfHour,
+    /// order by the performance of the day
+    PerfDay,
+    /// order by the performance of the week
+    PerfWeek,
+    /// order by the performance of the month
+    PerfMonth,
+    /// order by the performance of the quarter
+    PerfQuarter,
+    /// order by the performance of the half year
+    PerfHalfYear,
+    /// order by the performance of the year
+    PerfYear,
+    /// order by the performance of the year to date
+    PerfYearToDate,
+}
+
+impl fmt::Display for PerformanceOrder {
+
+    /// Formats the PerformanceOrder enum as a string to be used in URL parameters.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            PerformanceOrder::Ticker => "ticker",
+            PerformanceOrder::Price => "price",
+            PerformanceOrder::Perf5Min => "perf5minpct",
+            PerformanceOrder::PerfHour => "perfhourpct",
+            PerformanceOrder::PerfDay => "perfdaypct",
+            PerformanceOrder::PerfWeek => "perfweekpct",
+            PerformanceOrder::PerfMonth => "perfmonthpct",
+            PerformanceOrder::PerfQuarter => "perfquarterpct",
+            PerformanceOrder::PerfHalfYear => "perfhalfpct",
+            PerformanceOrder::PerfYear => "perfyearpct",
+            PerformanceOrder::PerfYearToDate => "perfytdpct",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+/// A typed row of the performance tables of `Forex` and `Crypto`, in the order of their `default_header`
+#[derive(Clone, Debug, PartialEq)]
+pub struct PerformanceRow {
+    /// ticker of the forex pair or the cryptocurrency
+    pub ticker: String,
+    /// latest price
+    pub price: Option<f64>,
+    /// unit of the performance fields
+    pub unit: PerformanceUnit,
+    /// performance of 5 minutes
+    pub perf_5min: Option<f64>,
+    /// performance of the hour
+    pub perf_hour: Option<f64>,
+    /// performance of the day
+    pub perf_day: Option<f64>,
+    /// performance of the week
+    pub perf_week: Option<f64>,
+    /// performance of the month
+    pub perf_month: Option<f64>,
+    /// performance of the quarter
+    pub perf_quarter: Option<f64>,
+    /// performance of the half year
+    pub perf_half_year: Option<f64>,
+    /// performance of the year
+    pub perf_year: Option<f64>,
+    /// performance of the year to date
+    pub perf_ytd: Option<f64>,
+}
+
+impl PerformanceRow {
+
+    /// Converts the rows returned by `Forex::scrape` or `Crypto::scrape` with the performance in the unit.
+    pub fn from_table(table: &TableData, unit: PerformanceUnit) -> Result<Vec<Self>, Box<dyn std::error::Error>> {
+        table.iter().map(|row| PerformanceRow::from_row(row, unit)).collect()
+    }
+
+    /// Converts a row returned by `Forex::scrape` or `Crypto::scrape` with the performance in the unit.
+    pub fn from_row(row: &[String], unit: PerformanceUnit) -> Result<Self, Box<dyn std::error::Error>> {
+        let [ticker, price, perf_5min, perf_hour, perf_day, perf_week, perf_month, perf_quarter, perf_half_year, perf_year, perf_ytd] = row else {
+            return Err(format!("Expect {} columns of performance, but got {}", PerformanceRow::header().len(), row.len()).into());
+        };
+        let perf = |text: &String| parse_performance(text, unit);
+
+        Ok(PerformanceRow {
+            ticker: ticker.trim().to_string(),
+            price: parse_number(price),
+            unit,
+            perf_5min: perf(perf_5min),
+            perf_hour: perf(perf_hour),
+            perf_day: perf(perf_day),
+            perf_week: perf(perf_week),
+            perf_month: perf(perf_month),
+            perf_quarter: perf(perf_quarter),
+            perf_half_year: perf(perf_half_year),
+            perf_year: perf(perf_year),
+            perf_ytd: perf(perf_ytd),
+        })
+    }
+}
+
+impl TableRow for PerformanceRow {
+
+    fn header() -> Vec<String> {
+        [
+            "Ticker", "Price", "Perf 5Min", "Perf Hour", "Perf Day", "Perf Week", "Perf Month",
+            "Perf Quart", "Perf Half", "Perf Year", "Perf YTD",
+        ].map(String::from).to_vec()
+    }
+
+    fn to_row(&self) -> Vec<String> {
+        let format = |value: Option<f64>| match (value, self.unit) {
+            (None, _) => "-".to_string(),
+            (Some(v), PerformanceUnit::Percent) => format!("{:.2}%", v),
+            (Some(v), PerformanceUnit::Pips) => format!("{}", v),
+        };
+        let mut row = vec![self.ticker.to_owned(), self.price.map_or("-".to_string(), |v| v.to_string())];
+        row.extend([
+            self.perf_5min, self.perf_hour, self.perf_day, self.perf_week, self.perf_month,
+            self.perf_quarter, self.perf_half_year, self.perf_year, self.perf_ytd,
+        ].map(format));
+        row
+    }
+}
+
+/// Parses a performance cell in the unit. A percentage like "1.50%" is only accepted as
+/// `PerformanceUnit::Percent`, and a plain number like "12.3" as `PerformanceUnit::Pips`, so a table
+/// parsed in the wrong unit has no values instead of wrong ones.
+///
+/// # Example
+///
+/// ```
+/// use finviz_rs::performance::{parse_performance, PerformanceUnit};
+///
+/// assert_eq!(parse_performance("-0.25%", PerformanceUnit::Percent), Some(-0.25));
+/// assert_eq!(parse_performance("1,234.5", PerformanceUnit::Pips), Some(1234.5));
+/// assert_eq!(parse_performance("-0.25%", PerformanceUnit::Pips), None);
+/// assert_eq!(parse_performance("-", PerformanceUnit::Percent), None);
+/// ```
+pub fn parse_performance(text: &str, unit: PerformanceUnit) -> Option<f64> {
+    let text = text.trim();
+    let is_percent = text.ends_with('%');
+    match unit {
+        PerformanceUnit::Percent if is_percent => parse_number(text),
+        PerformanceUnit::Pips if !is_percent => text.replace(',', "").parse::<f64>().ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(cells: &[&str]) -> Vec<String> {
+        cells.iter().map(|cell| cell.to_string()).collect()
+    }
+
+    #[test]
+    fn test_percent_row() {
+        let cells = row(&["EURUSD", "1.0856", "0.01%", "-0.05%", "0.32%", "1.10%", "-", "2.00%", "3.00%", "4.00%", "0.50%"]);
+        let perf = PerformanceRow::from_row(&cells, PerformanceUnit::Percent).unwrap();
+        assert_eq!(perf.ticker, "EURUSD");
+        assert_eq!(perf.price, Some(1.0856));
+        assert_eq!(perf.perf_hour, Some(-0.05));
+        assert_eq!(perf.perf_month, None);
+        assert_eq!(perf.perf_ytd, Some(0.5));
+        assert_eq!(perf.to_row()[4], "0.32%");
+        assert_eq!(perf.to_row()[6], "-");
+    }
+
+    #[test]
+    fn test_pips_row() {
+        let cells = row(&["USDJPY", "139.21", "1.2", "-3.4", "25", "-120.5", "1,050", "0", "10", "20", "30"]);
+        let perf = PerformanceRow::from_table(&vec![cells], PerformanceUnit::Pips).unwrap().remove(0);
+        assert_eq!(perf.unit, PerformanceUnit::Pips);
+        assert_eq!(perf.perf_day, Some(25.0));
+        assert_eq!(perf.perf_week, Some(-120.5));
+        assert_eq!(perf.perf_month, Some(1050.0));
+        assert_eq!(perf.to_row()[5], "-120.5");
+        assert!(PerformanceRow::from_row(&row(&["USDJPY", "139.21"]), PerformanceUnit::Pips).is_err());
+    }
+}
@@ -0,0 +1,322 @@
+use crate::common::{Scrape, TableRow};
+use crate::group::{Group, GroupBy, GroupPerformanceRow, GroupType, OrderBy, Ordering};
+use std::fmt;
+use strum::{EnumIter, IntoEnumIterator};
+
+/// Represents the performance timeframes of `GroupPerformanceRow` used in `SectorRotation`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, EnumIter)]
+pub enum RotationTimeframe {
+    /// performance of the week
+    Week,
+    /// performance of the month
+    Month,
+    /// performance of the quarter
+    Quarter,
+    /// performance of the half year
+    HalfYear,
+    /// performance of the year
+    Year,
+    /// performance of the year to date
+    YearToDate,
+}
+
+impl RotationTimeframe {
+
+    /// Returns the performance of the row in the timeframe.
+    pub fn performance(self, row: &GroupPerformanceRow) -> Option<f64> {
+        match self {
+            RotationTimeframe::Week => row.perf_week,
+            RotationTimeframe::Month => row.perf_month,
+            RotationTimeframe::Quarter => row.perf_quarter,
+            RotationTimeframe::HalfYear => row.perf_half_year,
+            RotationTimeframe::Year => row.perf_year,
+            RotationTimeframe::YearToDate => row.perf_ytd,
+        }
+    }
+}
+
+impl fmt::Display for RotationTimeframe {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RotationTimeframe::Week => write!(f, "Week"),
+            RotationTimeframe::Month => write!(f, "Month"),
+            RotationTimeframe::Quarter => write!(f, "Quarter"),
+            RotationTimeframe::HalfYear => write!(f, "Half"),
+            RotationTimeframe::Year => write!(f, "Year"),
+            RotationTimeframe::YearToDate => write!(f, "YTD"),
+        }
+    }
+}
+
+/// Represents the quadrant of a group in the rotation, by its strength over the long timeframe and
+/// its momentum over the short timeframe relative to the other groups
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RotationQuadrant {
+    /// strong and gaining momentum
+    Leading,
+    /// strong but losing momentum
+    Weakening,
+    /// weak and losing momentum
+    Lagging,
+    /// weak but gaining momentum
+    Improving,
+}
+
+impl fmt::Display for RotationQuadrant {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RotationQuadrant::Leading => write!(f, "Leading"),
+            RotationQuadrant::Weakening => write!(f, "Weakening"),
+            RotationQuadrant::Lagging => write!(f, "Lagging"),
+            RotationQuadrant::Improving => write!(f, "Improving"),
+        }
+    }
+}
+
+/// The rotation of a group, returned by `SectorRotation::analyze`
+#[derive(Clone, Debug, PartialEq)]
+pub struct GroupRotation {
+    /// name of the group
+    pub name: String,
+    /// rank of the performance among the groups per timeframe, where 1 is the best, and the
+    /// timeframes without the performance are skipped
+    pub ranks: Vec<(RotationTimeframe, usize)>,
+    /// rank of the long timeframe minus rank of the short timeframe, positive when the group is
+    /// climbing the ranks
+    pub rank_change: Option<i64>,
+    /// performance of the long timeframe minus the median of the groups
+    pub strength: Option<f64>,
+    /// performance of the short timeframe minus the median of the groups
+    pub momentum: Option<f64>,
+    /// quadrant by the signs of the strength and the momentum
+    pub quadrant: Option<RotationQuadrant>,
+}
+
+impl GroupRotation {
+
+    /// Returns the rank of the timeframe.
+    pub fn rank(&self, timeframe: RotationTimeframe) -> Option<usize> {
+        self.ranks.iter().find(|(t, _)| *t == timeframe).map(|(_, rank)| *rank)
+    }
+}
+
+impl TableRow for GroupRotation {
+
+    fn header() -> Vec<String> {
+        let mut header = vec!["Name".to_string()];
+        header.extend(RotationTimeframe::iter().map(|t| format!("Rank {}", t)));
+        header.extend(["Rank Change", "Strength", "Momentum", "Quadrant"].map(String::from));
+        header
+    }
+
+    fn to_row(&self) -> Vec<String> {
+        let optional = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+        let mut row = vec![self.name.to_owned()];
+        row.extend(RotationTimeframe::iter().map(|t| optional(self.rank(t).map(|r| r.to_string()))));
+        row.push(optional(self.rank_change.map(|v| v.to_string())));
+        row.push(optional(self.strength.map(|v| format!("{:.2}", v))));
+        row.push(optional(self.momentum.map(|v| format!("{:.2}", v))));
+        row.push(optional(self.quadrant.map(|q| q.to_string())));
+        row
+    }
+}
+
+/// This struct analyses the rotation of the groups from the results of `GroupType::Performance`. The
+/// groups are ranked per timeframe, and classified into quadrants by the strength over the long
+/// timeframe (a quarter by default) and the momentum over the short timeframe (a month by default),
+/// both relative to the median of the groups.
+///
+/// # Example
+///
+/// ```
+/// use finviz_rs::{
+///     group::GroupBy,
+///     rotation::{SectorRotation, RotationTimeframe},
+///     output::ToTable,
+/// };
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(),Box<dyn std::error::Error>>{
+///     let rows = SectorRotation::scrape_rows(GroupBy::Sector).await?;
+///     let rotation = SectorRotation::new(&rows)
+///         .set_timeframes(RotationTimeframe::Week, RotationTimeframe::HalfYear)
+///         .analyze();
+///     println!("{}", rotation.to_table(None, None));
+///     Ok(())
+/// }
+/// ```
+///
+/// The above example demonstrates how to classify the sectors by the performance of the week against the half year.
+pub struct SectorRotation<'a> {
+    rows: &'a [GroupPerformanceRow],
+    short: RotationTimeframe,
+    long: RotationTimeframe,
+}
+
+impl<'a> SectorRotation<'a> {
+
+    /// Creates a new `SectorRotation` over the rows of the groups.
+    pub fn new(rows: &'a [GroupPerformanceRow]) -> Self {
+        Self { rows, short: RotationTimeframe::Month, long: RotationTimeframe::Quarter }
+    }
+
+    /// Scrapes the performance of the groups, e.g. `GroupBy::Sector` or `GroupBy::Industry`.
+    pub async fn scrape_rows(group_by: GroupBy) -> Result<Vec<GroupPerformanceRow>, Box<dyn std::error::Error>> {
+        let table = Group::new(group_by, GroupType::Performance, OrderBy::Name, Ordering::Ascending)
+            .scrape().await?;
+        GroupPerformanceRow::from_table(&table)
+    }
+
+    /// Sets the timeframes of the momentum and the strength.
+    pub fn set_timeframes(&mut self, short: RotationTimeframe, long: RotationTimeframe) -> &mut Self {
+        self.short = short;
+        self.long = long;
+        self
+    }
+
+    /// Ranks and classifies the groups, ordered by the rank of the long timeframe.
+    pub fn analyze(&self) -> Vec<GroupRotation> {
+        let ranks = RotationTimeframe::iter()
+            .map(|timeframe| (timeframe, rank(self.rows, timeframe)))
+            .collect::<Vec<_>>();
+        let short_median = median(self.rows, self.short);
+        let long_median = median(self.rows, self.long);
+
+        let mut rotations = self.rows.iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let group_ranks = ranks.iter()
+                    .filter_map(|(timeframe, ranks)| ranks[i].map(|rank| (*timeframe, rank)))
+                    .collect::<Vec<_>>();
+                let find_rank = |timeframe| group_ranks.iter().find(|(t, _)| *t == timeframe).map(|(_, rank)| *rank as i64);
+
+                let strength = self.long.performance(row).zip(long_median).map(|(perf, median)| perf - median);
+                let momentum = self.short.performance(row).zip(short_median).map(|(perf, median)| perf - median);
+                GroupRotation {
+                    name: row.name.to_owned(),
+                    rank_change: find_rank(self.long).zip(find_rank(self.short)).map(|(long, short)| long - short),
+                    ranks: group_ranks,
+                    strength,
+                    momentum,
+                    quadrant: strength.zip(momentum).map(|(strength, momentum)| quadrant(strength, momentum)),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        rotations.sort_by_key(|r| r.rank(self.long).unwrap_or(usize::MAX));
+        rotations
+    }
+}
+
+// helper function to classify the quadrant by the signs of the strength and the momentum
+fn quadrant(strength: f64, momentum: f64) -> RotationQuadrant {
+    match (strength >= 0.0, momentum >= 0.0) {
+        (true, true) => RotationQuadrant::Leading,
+        (true, false) => RotationQuadrant::Weakening,
+        (false, false) => RotationQuadrant::Lagging,
+        (false, true) => RotationQuadrant::Improving,
+    }
+}
+
+// helper function to rank the rows by the performance of the timeframe from the best, where the
+// ties share the same rank
+fn rank(rows: &[GroupPerformanceRow], timeframe: RotationTimeframe) -> Vec<Option<usize>> {
+    let performances = rows.iter().map(|row| timeframe.performance(row)).collect::<Vec<_>>();
+    performances.iter()
+        .map(|perf| perf.map(|perf| 1 + performances.iter().flatten().filter(|other| **other > perf).count()))
+        .collect()
+}
+
+// helper function to return the median performance of the timeframe
+fn median(rows: &[GroupPerformanceRow], timeframe: RotationTimeframe) -> Option<f64> {
+    let mut performances = rows.iter().filter_map(|row| timeframe.performance(row)).collect::<Vec<_>>();
+    if performances.is_empty() {
+        return None;
+    }
+    performances.sort_by(f64::total_cmp);
+    let middle = performances.len() / 2;
+    if performances.len() % 2 == 0 {
+        Some((performances[middle - 1] + performances[middle]) / 2.0)
+    } else {
+        Some(performances[middle])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(name: &str, perf_month: Option<f64>, perf_quarter: Option<f64>) -> GroupPerformanceRow {
+        GroupPerformanceRow {
+            name: name.to_string(),
+            perf_week: None,
+            perf_month,
+            perf_quarter,
+            perf_half_year: None,
+            perf_year: None,
+            perf_ytd: None,
+            avg_volume: None,
+            rel_volume: None,
+            change: None,
+            volume: None,
+        }
+    }
+
+    fn rows() -> Vec<GroupPerformanceRow> {
+        vec![
+            row("Energy", Some(-3.0), Some(8.0)),
+            row("Technology", Some(6.0), Some(12.0)),
+            row("Utilities", Some(-4.0), Some(-5.0)),
+            row("Healthcare", Some(4.0), Some(-2.0)),
+            row("Financial", None, Some(1.0)),
+        ]
+    }
+
+    #[test]
+    fn test_rank_and_median() {
+        let rows = rows();
+        assert_eq!(rank(&rows, RotationTimeframe::Month), vec![Some(3), Some(1), Some(4), Some(2), None]);
+        assert_eq!(median(&rows, RotationTimeframe::Month), Some(0.5));
+        assert_eq!(median(&rows, RotationTimeframe::Quarter), Some(1.0));
+        assert_eq!(median(&rows, RotationTimeframe::Week), None);
+
+        let ties = vec![row("A", Some(1.0), None), row("B", Some(1.0), None), row("C", Some(0.0), None)];
+        assert_eq!(rank(&ties, RotationTimeframe::Month), vec![Some(1), Some(1), Some(3)]);
+    }
+
+    #[test]
+    fn test_analyze() {
+        let rows = rows();
+        let rotations = SectorRotation::new(&rows).analyze();
+        let names = rotations.iter().map(|r| r.name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, vec!["Technology", "Energy", "Financial", "Healthcare", "Utilities"]);
+
+        let quadrants = rotations.iter().map(|r| r.quadrant).collect::<Vec<_>>();
+        assert_eq!(quadrants, vec![
+            Some(RotationQuadrant::Leading),
+            Some(RotationQuadrant::Weakening),
+            None,
+            Some(RotationQuadrant::Improving),
+            Some(RotationQuadrant::Lagging),
+        ]);
+        assert_eq!(rotations[3].rank(RotationTimeframe::Quarter), Some(4));
+        assert_eq!(rotations[3].rank_change, Some(2));
+        assert_eq!(rotations[1].rank_change, Some(-1));
+        assert_eq!(rotations[1].strength, Some(7.0));
+        assert_eq!(rotations[1].momentum, Some(-3.5));
+        assert_eq!(rotations[0].to_row()[1], "-");
+        assert_eq!(rotations[0].to_row().len(), GroupRotation::header().len());
+    }
+
+    #[test]
+    fn test_set_timeframes() {
+        let rows = rows();
+        let rotations = SectorRotation::new(&rows)
+            .set_timeframes(RotationTimeframe::Quarter, RotationTimeframe::Month)
+            .analyze();
+        assert_eq!(rotations[0].name, "Technology");
+        assert_eq!(rotations[1].name, "Healthcare");
+        assert_eq!(rotations[1].quadrant, Some(RotationQuadrant::Weakening));
+        assert_eq!(rotations[4].quadrant, None);
+    }
+}
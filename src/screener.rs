@@ -40,11 +40,24 @@ const BASE_URL: &str = "https://finviz.com/screener.ashx?";
 /// ```
 ///
 /// The above example demonstrates how to retrive the Overview of screener page into a table and print it.
+///
+/// The filters of finviz are added by their ids, e.g. the stocks of the semiconductors industry in the USA:
+///
+/// ```
+/// use finviz_rs::{screener::Screener, screener_type::ScreenerType};
+///
+/// let url = Screener::new(ScreenerType::Overview)
+///     .add_filter("ind_semiconductors")
+///     .add_filter("geo_usa")
+///     .to_url();
+/// assert_eq!(url, "https://finviz.com/screener.ashx?v=111&f=ind_semiconductors,geo_usa");
+/// ```
 pub struct Screener {
     base_type: ScreenerType,
     signal_type: Option<SignalType>,
     order_type: Option<OrderType>,
-    ordering: Ordering
+    ordering: Ordering,
+    filters: Vec<String>,
 }
 
 impl Default for Screener {
@@ -57,7 +70,16 @@ impl Screener {
 
     /// Creates a new `Screener` instance with the specified `ScreenerType`.
     pub fn new(base_type: ScreenerType) ->  Self {
-        Self {base_type, signal_type: None, order_type: None, ordering: Ordering::Ascending }
+        Self {base_type, signal_type: None, order_type: None, ordering: Ordering::Ascending, filters: Vec::new() }
+    }
+
+    /// Adds a filter by the id used on finviz, e.g. "sec_technology" or "cap_large".
+    pub fn add_filter(&mut self, filter: &str) -> &mut Self {
+        let filter = filter.trim().to_string();
+        if !filter.is_empty() && !self.filters.contains(&filter) {
+            self.filters.push(filter);
+        }
+        self
     }
 
     /// Sets the signal type for the screener.
@@ -82,8 +104,9 @@ impl Screener {
 
     /// Generates the URL based on the current screener configuration.
     pub fn to_url(&self) ->  String {
-        format!("{}v={}{}{}", BASE_URL,
+        format!("{}v={}{}{}{}", BASE_URL,
                             self.base_type, 
+                            if self.filters.is_empty() { String::new() } else { format!("&f={}", self.filters.join(",")) },
                             self.signal_type.as_ref().map_or(String::new(), |s| format!("&s={}", s)),
                             self.order_type.as_ref().map_or(String::new(), |s| format!("&o={}{}", self.ordering, s))
                )
@@ -136,4 +159,12 @@ mod tests {
         assert_eq!(screener.to_url(), "https://finviz.com/screener.ashx?v=141&s=ta_toplosers&o=-ticker");
     }
 
+    #[test]
+    fn test_base_url_with_filters() {
+        let mut screener = Screener::new(ScreenerType::Performance);
+        screener.add_filter("sec_technology").add_filter(" cap_large ").add_filter("sec_technology");
+        screener.set_signal(SignalType::TopGainers);
+        assert_eq!(screener.to_url(), "https://finviz.com/screener.ashx?v=141&f=sec_technology,cap_large&s=ta_topgainers");
+    }
+
 }
@@ -0,0 +1,336 @@
+use crate::common::TableRow;
+use crate::news::{NewsData, NewsItem};
+use chrono::{DateTime, NaiveDate};
+use chrono_tz::Tz;
+use std::collections::{BTreeMap, HashMap};
+
+/// Words of the built-in lexicon that are bullish for a company
+const POSITIVE_WORDS: &[(&str, f64)] = &[
+    ("beat", 1.0), ("beats", 1.0), ("tops", 1.0), ("surge", 1.0), ("surges", 1.0), ("soar", 1.0),
+    ("soars", 1.0), ("jump", 0.8), ("jumps", 0.8), ("rally", 0.8), ("rallies", 0.8), ("gain", 0.6),
+    ("gains", 0.6), ("rise", 0.5), ("rises", 0.5), ("climb", 0.5), ("climbs", 0.5), ("record", 0.6),
+    ("upgrade", 1.0), ("upgrades", 1.0), ("upgraded", 1.0), ("outperform", 0.8), ("bullish", 1.0),
+    ("buy", 0.5), ("growth", 0.5), ("profit", 0.5), ("profitable", 0.6), ("strong", 0.6),
+    ("raises", 0.6), ("boost", 0.6), ("boosts", 0.6), ("expands", 0.4), ("approval", 0.8),
+    ("approved", 0.8), ("wins", 0.8), ("dividend", 0.3), ("buyback", 0.6), ("rebound", 0.6),
+    ("rebounds", 0.6), ("recovery", 0.5), ("optimism", 0.6), ("breakthrough", 0.8),
+];
+
+/// Words of the built-in lexicon that are bearish for a company
+const NEGATIVE_WORDS: &[(&str, f64)] = &[
+    ("miss", -1.0), ("misses", -1.0), ("plunge", -1.0), ("plunges", -1.0), ("tumble", -1.0),
+    ("tumbles", -1.0), ("slump", -0.8), ("slumps", -0.8), ("fall", -0.5), ("falls", -0.5),
+    ("drop", -0.5), ("drops", -0.5), ("sink", -0.7), ("sinks", -0.7), ("slide", -0.6),
+    ("slides", -0.6), ("downgrade", -1.0), ("downgrades", -1.0), ("downgraded", -1.0),
+    ("underperform", -0.8), ("bearish", -1.0), ("sell", -0.5), ("loss", -0.6), ("losses", -0.6),
+    ("weak", -0.6), ("cuts", -0.5), ("layoffs", -0.7), ("lawsuit", -0.7), ("probe", -0.6),
+    ("investigation", -0.6), ("recall", -0.7), ("fraud", -1.0), ("bankruptcy", -1.0),
+    ("default", -0.8), ("warns", -0.8), ("warning", -0.7), ("crash", -1.0), ("selloff", -0.8),
+    ("recession", -0.7), ("fears", -0.6), ("halt", -0.6), ("delisted", -1.0),
+];
+
+/// Words that flip the sign of the next scored word
+const NEGATIONS: &[&str] = &["not", "no", "never", "without", "fails", "failed"];
+
+/// The number of tokens after a negation within which a word of the lexicon is negated, e.g. "fails
+/// to beat" or "not a big miss"
+const NEGATION_WINDOW: usize = 3;
+
+/// The sentiment score of a headline, returned by `SentimentAnalyzer::score_news`
+#[derive(Clone, Debug, PartialEq)]
+pub struct HeadlineSentiment {
+    /// time when the headline was published
+    pub published: DateTime<Tz>,
+    /// title of the headline
+    pub title: String,
+    /// tickers labeled on the headline
+    pub tickers: Vec<String>,
+    /// score from -1 (bearish) to 1 (bullish), 0 when no word of the lexicon is found
+    pub score: f64,
+}
+
+impl TableRow for HeadlineSentiment {
+
+    fn header() -> Vec<String> {
+        ["Time", "Title", "Tickers", "Score"].map(String::from).to_vec()
+    }
+
+    fn to_row(&self) -> Vec<String> {
+        vec![
+            self.published.format("%Y-%m-%d %H:%M").to_string(),
+            self.title.to_owned(),
+            self.tickers.join(", "),
+            format!("{:.2}", self.score),
+        ]
+    }
+}
+
+/// The daily sentiment of a ticker, returned by `SentimentAnalyzer::by_ticker`
+#[derive(Clone, Debug, PartialEq)]
+pub struct TickerSentiment {
+    /// ticker of the headlines
+    pub ticker: String,
+    /// date of the headlines in the time zone of finviz
+    pub date: NaiveDate,
+    /// number of headlines
+    pub headlines: usize,
+    /// number of headlines with a positive score
+    pub bullish: usize,
+    /// number of headlines with a negative score
+    pub bearish: usize,
+    /// average score of the headlines
+    pub score: f64,
+}
+
+impl TableRow for TickerSentiment {
+
+    fn header() -> Vec<String> {
+        ["Ticker", "Date", "Headlines", "Bullish", "Bearish", "Score"].map(String::from).to_vec()
+    }
+
+    fn to_row(&self) -> Vec<String> {
+        vec![
+            self.ticker.to_owned(),
+            self.date.to_string(),
+            self.headlines.to_string(),
+            self.bullish.to_string(),
+            self.bearish.to_string(),
+            format!("{:.2}", self.score),
+        ]
+    }
+}
+
+/// This struct scores headlines by a finance lexicon, without any external service. The score of a
+/// headline is the average weight of the words found in the lexicon, where a negation like "not"
+/// flips the next word of the lexicon within 3 words, e.g. "fails to beat".
+///
+/// # Example
+///
+/// ```
+/// use finviz_rs::{
+///     news::{News, NewsView},
+///     sentiment::SentimentAnalyzer,
+///     output::ToTable,
+///     common::Scrape,
+/// };
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(),Box<dyn std::error::Error>>{
+///     let news = News::default()
+///         .set_view(NewsView::Stocks)
+///         .scrape().await?;
+///
+///     let mut analyzer = SentimentAnalyzer::default();
+///     analyzer.add_positive(&["fda", "partnership"]).add_negative(&["short seller"]);
+///     println!("{}", analyzer.score_news(&news).to_table(None, Some(5)));
+///     println!("{}", analyzer.by_ticker(&news).to_table(None, Some(5)));
+///     Ok(())
+/// }
+/// ```
+///
+/// The above example demonstrates how to extend the lexicon, score the headlines of stocks, and aggregate them per ticker and day.
+#[derive(Clone, Debug)]
+pub struct SentimentAnalyzer {
+    lexicon: HashMap<String, f64>,
+}
+
+impl Default for SentimentAnalyzer {
+
+    /// Create a `SentimentAnalyzer` with the built-in finance lexicon
+    fn default() -> Self {
+        let lexicon = POSITIVE_WORDS.iter().chain(NEGATIVE_WORDS)
+            .map(|(word, weight)| (word.to_string(), *weight))
+            .collect();
+        Self { lexicon }
+    }
+}
+
+impl SentimentAnalyzer {
+
+    /// Create a `SentimentAnalyzer` with an empty lexicon.
+    pub fn empty() -> Self {
+        Self { lexicon: HashMap::new() }
+    }
+
+    /// Adds bullish words with the weight 1. A word can be a phrase of several words.
+    pub fn add_positive(&mut self, words: &[&str]) -> &mut Self {
+        words.iter().for_each(|word| { self.set_word(word, 1.0); });
+        self
+    }
+
+    /// Adds bearish words with the weight -1. A word can be a phrase of several words.
+    pub fn add_negative(&mut self, words: &[&str]) -> &mut Self {
+        words.iter().for_each(|word| { self.set_word(word, -1.0); });
+        self
+    }
+
+    /// Sets the weight of a word, from -1 (bearish) to 1 (bullish), replacing the built-in one.
+    pub fn set_word(&mut self, word: &str, weight: f64) -> &mut Self {
+        self.lexicon.insert(tokenize(word).join(" "), weight.clamp(-1.0, 1.0));
+        self
+    }
+
+    /// Removes a word from the lexicon.
+    pub fn remove_word(&mut self, word: &str) -> &mut Self {
+        self.lexicon.remove(&tokenize(word).join(" "));
+        self
+    }
+
+    /// Scores a text from -1 (bearish) to 1 (bullish), or 0 when no word of the lexicon is found.
+    pub fn score(&self, text: &str) -> f64 {
+        let tokens = tokenize(text);
+        let max_words = self.lexicon.keys().map(|word| word.split(' ').count()).max().unwrap_or(1);
+
+        let mut weights = Vec::new();
+        // the number of the following tokens that are still negated
+        let mut negated = 0;
+        let mut i = 0;
+        while i < tokens.len() {
+            // the longest phrase of the lexicon starting at the token
+            let matched = (1..=max_words.min(tokens.len() - i)).rev()
+                .find_map(|n| self.lexicon.get(&tokens[i..i + n].join(" ")).map(|weight| (n, *weight)));
+
+            match matched {
+                Some((n, weight)) => {
+                    weights.push(if negated > 0 { -weight } else { weight });
+                    negated = 0;
+                    i += n;
+                },
+                None => {
+                    negated = if NEGATIONS.contains(&tokens[i].as_str()) { NEGATION_WINDOW } else { negated.saturating_sub(1) };
+                    i += 1;
+                },
+            }
+        }
+
+        if weights.is_empty() {
+            return 0.0;
+        }
+        (weights.iter().sum::<f64>() / weights.len() as f64).clamp(-1.0, 1.0)
+    }
+
+    /// Scores the titles of both news and blogs.
+    pub fn score_news(&self, data: &NewsData) -> Vec<HeadlineSentiment> {
+        self.score_items(data.news.iter().chain(data.blogs.iter()))
+    }
+
+    /// Scores the titles of the items.
+    pub fn score_items<'a, I>(&self, items: I) -> Vec<HeadlineSentiment>
+    where
+        I: IntoIterator<Item = &'a NewsItem>,
+    {
+        items.into_iter()
+            .map(|item| HeadlineSentiment {
+                published: item.published,
+                title: item.title.to_owned(),
+                tickers: item.tickers.to_owned(),
+                score: self.score(&item.title),
+            })
+            .collect()
+    }
+
+    /// Aggregates the scores of the headlines labeled with tickers per ticker and day, ordered by
+    /// ticker and date. The headlines without tickers are skipped.
+    pub fn by_ticker(&self, data: &NewsData) -> Vec<TickerSentiment> {
+        aggregate_by_ticker(&self.score_news(data))
+    }
+}
+
+/// Aggregates the scores of the headlines per ticker and day, ordered by ticker and date.
+pub fn aggregate_by_ticker(headlines: &[HeadlineSentiment]) -> Vec<TickerSentiment> {
+    let mut groups: BTreeMap<(&str, NaiveDate), Vec<f64>> = BTreeMap::new();
+    for headline in headlines {
+        for ticker in headline.tickers.iter() {
+            groups.entry((ticker, headline.published.date_naive())).or_default().push(headline.score);
+        }
+    }
+
+    groups.into_iter()
+        .map(|((ticker, date), scores)| TickerSentiment {
+            ticker: ticker.to_string(),
+            date,
+            headlines: scores.len(),
+            bullish: scores.iter().filter(|score| **score > 0.0).count(),
+            bearish: scores.iter().filter(|score| **score < 0.0).count(),
+            score: scores.iter().sum::<f64>() / scores.len() as f64,
+        })
+        .collect()
+}
+
+// helper function to split a text into lowercase words, keeping the apostrophes within words
+fn tokenize(text: &str) -> Vec<String> {
+    text.to_lowercase()
+        .split(|c: char| !(c.is_alphanumeric() || c == '\''))
+        .map(|word| word.trim_matches('\''))
+        .filter(|word| !word.is_empty())
+        .map(|word| word.trim_end_matches("'s").to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::news::NewsKind;
+    use chrono::TimeZone;
+
+    fn item(title: &str, tickers: &[&str], day: u32) -> NewsItem {
+        NewsItem {
+            published: chrono_tz::America::New_York.with_ymd_and_hms(2023, 5, day, 9, 30, 0).unwrap(),
+            title: title.to_string(),
+            source: "www.wsj.com".to_string(),
+            url: format!("https://www.wsj.com/{}", title),
+            kind: NewsKind::News,
+            tickers: tickers.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_score() {
+        let analyzer = SentimentAnalyzer::default();
+        assert_eq!(analyzer.score("Apple beats estimates, shares surge"), 1.0);
+        assert_eq!(analyzer.score("Tesla misses on deliveries"), -1.0);
+        assert_eq!(analyzer.score("Fed holds rates steady"), 0.0);
+        assert_eq!(analyzer.score("Revenue does not miss"), 1.0);
+        assert_eq!(analyzer.score("Company fails to beat estimates"), -1.0);
+        assert_eq!(analyzer.score("Company failed to beat the estimates"), -1.0);
+        assert_eq!(analyzer.score("No change as the company will soon beat"), 1.0);
+        assert!((analyzer.score("Stock rises despite lawsuit") - (-0.1)).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_custom_words() {
+        let mut analyzer = SentimentAnalyzer::empty();
+        assert_eq!(analyzer.score("Company beats"), 0.0);
+
+        analyzer.add_negative(&["Short Seller"]).add_positive(&["FDA"]).set_word("guidance", 0.4);
+        assert_eq!(analyzer.score("Short seller targets biotech"), -1.0);
+        assert_eq!(analyzer.score("FDA's nod lifts guidance"), 0.7);
+
+        analyzer.remove_word("fda");
+        assert_eq!(analyzer.score("FDA's nod"), 0.0);
+    }
+
+    #[test]
+    fn test_by_ticker() {
+        let data = NewsData {
+            news: vec![
+                item("AAPL beats", &["AAPL"], 15),
+                item("AAPL and MSFT plunge", &["AAPL", "MSFT"], 15),
+                item("AAPL upgrade", &["AAPL"], 14),
+                item("Markets rally", &[], 15),
+            ],
+            blogs: Vec::new(),
+        };
+        let analyzer = SentimentAnalyzer::default();
+        assert_eq!(analyzer.score_news(&data).len(), 4);
+
+        let sentiments = analyzer.by_ticker(&data);
+        assert_eq!(sentiments.len(), 3);
+        assert_eq!((sentiments[0].ticker.as_str(), sentiments[0].date.to_string()), ("AAPL", "2023-05-14".to_string()));
+        assert_eq!(sentiments[1].headlines, 2);
+        assert_eq!((sentiments[1].bullish, sentiments[1].bearish), (1, 1));
+        assert_eq!(sentiments[1].score, 0.0);
+        assert_eq!(sentiments[2].ticker, "MSFT");
+        assert_eq!(sentiments[2].score, -1.0);
+    }
+}
@@ -1,9 +1,13 @@
 use std::fmt;
 use std::collections::BTreeMap;
-use crate::web_scraper::{scrape_chart_image, get_html_body};
+use crate::web_scraper::{get_chart_image, get_html_body, save_image, ChartImage};
+use crate::chart::ChartRequest;
 use scraper::{Html, Selector};
-use crate::common::{DictData, Scrape};
-use std::fs;
+use crate::common::{DictData, Scrape, TableRow};
+use crate::parse::parse_number;
+use chrono::{DateTime, NaiveDate, NaiveDateTime};
+use serde::de::{Deserialize, Deserializer, IgnoredAny, MapAccess, Visitor};
+use serde_json::Value;
 use strum::EnumIter;
 use async_trait::async_trait;
 
@@ -39,17 +43,41 @@ pub struct Tickers {
 }
 
 #[doc(hidden)]
-#[derive(Clone, Copy, EnumIter)]
+#[derive(Clone, Copy, Debug, PartialEq, EnumIter)]
 pub enum TimeFrameType {
-    Daily, Weekly, Monthly
+    Minute1, Minute3, Minute5, Minute15, Minute30, Hourly, Daily, Weekly, Monthly
 }
 
 
+impl TimeFrameType {
+
+    /// Returns the `TimeFrameType` as the period parameter of finviz chart URLs
+    pub(crate) fn to_url_param(self) -> &'static str {
+        match self {
+            TimeFrameType::Minute1 => "i1",
+            TimeFrameType::Minute3 => "i3",
+            TimeFrameType::Minute5 => "i5",
+            TimeFrameType::Minute15 => "i15",
+            TimeFrameType::Minute30 => "i30",
+            TimeFrameType::Hourly => "h",
+            TimeFrameType::Daily => "d",
+            TimeFrameType::Weekly => "w",
+            TimeFrameType::Monthly => "m",
+        }
+    }
+}
+
 impl fmt::Display for TimeFrameType {
 
     /// Formats the `TimeFrameType` that can be used as URL parameter in `Tickers` 
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            TimeFrameType::Minute1 => write!(f, "1min"),
+            TimeFrameType::Minute3 => write!(f, "3min"),
+            TimeFrameType::Minute5 => write!(f, "5min"),
+            TimeFrameType::Minute15 => write!(f, "15min"),
+            TimeFrameType::Minute30 => write!(f, "30min"),
+            TimeFrameType::Hourly => write!(f, "hourly"),
             TimeFrameType::Daily => write!(f, "daily"),
             TimeFrameType::Weekly => write!(f, "weekly"),
             TimeFrameType::Monthly => write!(f, "monthly"),
@@ -58,7 +86,7 @@ impl fmt::Display for TimeFrameType {
 }
 
 #[doc(hidden)]
-#[derive(Clone, Copy, EnumIter)]
+#[derive(Clone, Copy, Debug, PartialEq, EnumIter)]
 pub enum ChartType {
     CANDLE, LINE, ADVANCED
 }
@@ -75,6 +103,25 @@ impl fmt::Display for ChartType {
     }
 }
 
+/// Represents the financial statement that can be retrieved by `Tickers::statements`
+#[derive(Clone, Copy, EnumIter)]
+pub enum StatementType {
+    /// income statement
+    IncomeStatement,
+    /// balance sheet
+    BalanceSheet,
+    /// cash flow statement
+    CashFlow,
+}
+
+/// Represents the reporting period of a financial statement
+#[derive(Clone, Copy, EnumIter)]
+pub enum Period {
+    /// annual statements
+    Annual,
+    /// quarterly statements
+    Quarterly,
+}
 
 impl Tickers {
 
@@ -86,37 +133,317 @@ impl Tickers {
     }
 
     /// Scrapes chart image by the given timeframe and chart type, return the String of the saved image path in
-    /// the specified output directory on success, or error string on failure
+    /// the specified output directory on success, or error string on failure. The file is named by
+    /// `ChartRequest::DEFAULT_FILE_NAME_TEMPLATE`, e.g. `AAPL_daily_advanced.png`, so charts of different timeframes
+    /// do not overwrite each other; use `chart` with a `ChartRequest` for other file names.
     pub async fn ticker_charts(
         &self,
         timeframe: TimeFrameType,
         charttype: ChartType,
         out_dir: &str,
     ) -> Result<String, Box<dyn std::error::Error>> {
+        self.chart(&Tickers::ticker_chart_request(timeframe, charttype), out_dir).await
+    }
 
-        let (url_type , url_ta) = match (&charttype, &timeframe) {
-            (ChartType::LINE, _) =>  ("l", "0"),
-            (ChartType::CANDLE, _) =>  ("c", "0"),
-            (ChartType::ADVANCED, TimeFrameType::Daily) =>  ("c", "1"),
-            (ChartType::ADVANCED, _) =>  ("c", "0"),
-        };
+    // helper function of the `ChartRequest` of `ticker_charts`
+    fn ticker_chart_request(timeframe: TimeFrameType, charttype: ChartType) -> ChartRequest {
+        let mut request = ChartRequest::new();
+        request.set_timeframe(timeframe).set_chart_type(charttype);
+        request
+    }
 
+    /// Scrapes chart image by the given `ChartRequest`, return the String of the saved image path in
+    /// the specified output directory on success, or error string on failure. The file name is generated
+    /// by `ChartRequest::to_file_name`, and the file is written atomically.
+    pub async fn chart(&self, request: &ChartRequest, out_dir: &str) -> Result<String, Box<dyn std::error::Error>> {
+        let image = self.chart_image(request).await?;
+        save_image(&image, out_dir, &request.to_file_name(&self.ticker))
+    }
 
-        let url_timeframe = match &timeframe {
-            TimeFrameType::Weekly => "w",
-            TimeFrameType::Monthly => "m",
-            TimeFrameType::Daily => "d",
+    /// Retrieves chart image by the given `ChartRequest` into memory, return the `ChartImage` on success,
+    /// or error on failure, e.g. when finviz responds with an HTML page instead of an image
+    pub async fn chart_image(&self, request: &ChartRequest) -> Result<ChartImage, Box<dyn std::error::Error>> {
+        get_chart_image(&request.to_url(&self.ticker)).await
+    }
+
+    /// Constructs the URL of the price data finviz uses to draw its charts.
+    fn get_history_url(&self, timeframe: TimeFrameType) -> String {
+        format!(
+            "https://finviz.com/api/quote.ashx?instrument=stock&ticker={}&timeframe={}",
+            self.ticker, timeframe.to_url_param()
+        )
+    }
+
+    /// Retrieves the price history (OHLCV) of the ticker by the given timeframe, return the bars
+    /// ordered from the oldest to the latest on success, or error on failure.
+    ///
+    /// The result implements the traits in `output`, e.g. `to_table` or `to_csv_file`.
+    pub async fn history(&self, timeframe: TimeFrameType) -> Result<Vec<Bar>, Box<dyn std::error::Error>> {
+        let body = get_html_body(&self.get_history_url(timeframe)).await?;
+        let data: Value = serde_json::from_str(&body)?;
+        parse_history(&data)
+    }
+}
+
+impl Tickers {
+
+    /// Constructs the URL of the financial statement with the specified parameters.
+    fn get_statement_url(&self, statement_type: StatementType, period: Period) -> String {
+        let url_statement = match statement_type {
+            StatementType::IncomeStatement => "I",
+            StatementType::BalanceSheet => "B",
+            StatementType::CashFlow => "C",
         };
+        let url_period = match period {
+            Period::Annual => "A",
+            Period::Quarterly => "Q",
+        };
+        format!("https://finviz.com/api/statement.ashx?t={}&s={}{}", self.ticker, url_statement, url_period)
+    }
+
+    /// Retrieves a financial statement of the ticker as shown on the quote page, return a
+    /// `Statement` with one column per period on success, or error on failure.
+    pub async fn statements(&self, statement_type: StatementType, period: Period) -> Result<Statement, Box<dyn std::error::Error>> {
+        let body = get_html_body(&self.get_statement_url(statement_type, period)).await?;
+        parse_statement(&body)
+    }
+}
+
+/// A line item of a `Statement`, e.g. "Total Revenue"
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineItem {
+    /// name of the line item
+    pub name: String,
+    /// values of the line item in the order of `Statement::periods`, `None` if not reported
+    pub values: Vec<Option<f64>>,
+}
+
+/// A financial statement returned by `Tickers::statements`
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Statement {
+    /// end dates of the periods, from the latest to the oldest as shown on finviz
+    pub periods: Vec<String>,
+    /// line items in the order shown on finviz
+    pub line_items: Vec<LineItem>,
+}
+
+impl Statement {
 
-        let chart_url = format!(
-            "https://finviz.com/chart.ashx?t={}&ty={}&ta={}&p={}",
-            self.ticker, url_type, url_ta, url_timeframe
-        );
+    /// Returns the line item by its name, e.g. "Net Income"
+    pub fn get(&self, name: &str) -> Option<&LineItem> {
+        self.line_items.iter().find(|item| item.name == name)
+    }
+
+    /// Converts the statement to one `StatementValue` per line item and period, in the order shown
+    /// on finviz, so it can be written by the traits in `output`
+    pub fn values(&self) -> Vec<StatementValue> {
+        self.line_items.iter()
+            .flat_map(|item| {
+                self.periods.iter().zip(item.values.iter()).map(|(period, value)| StatementValue {
+                    line_item: item.name.to_owned(),
+                    period: period.to_owned(),
+                    value: *value,
+                })
+            })
+            .collect()
+    }
+}
+
+/// A value of a `Statement` for a line item and a period, returned by `Statement::values`
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatementValue {
+    /// name of the line item, e.g. "Total Revenue"
+    pub line_item: String,
+    /// end date of the period
+    pub period: String,
+    /// the value, `None` if not reported
+    pub value: Option<f64>,
+}
+
+impl TableRow for StatementValue {
 
-        let out_dir = if out_dir.is_empty() { "." } else { out_dir } ;
-        fs::create_dir_all(out_dir).map_err(|err| err.to_string())?;
+    fn header() -> Vec<String> {
+        ["Line Item", "Period End Date", "Value"].map(String::from).to_vec()
+    }
 
-        scrape_chart_image(&chart_url, &self.ticker, out_dir).await
+    fn to_row(&self) -> Vec<String> {
+        vec![
+            self.line_item.to_owned(),
+            self.period.to_owned(),
+            self.value.map_or("-".to_string(), |v| v.to_string()),
+        ]
+    }
+}
+
+// The line items of the statement data in the order of the response, which a `serde_json::Value`
+// object does not keep
+struct StatementRows(Vec<(String, Value)>);
+
+impl<'de> Deserialize<'de> for StatementRows {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct DataVisitor;
+
+        impl<'de> Visitor<'de> for DataVisitor {
+            type Value = StatementRows;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "an object of line items")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<StatementRows, A::Error> {
+                let mut rows = Vec::new();
+                while let Some(entry) = map.next_entry::<String, Value>()? {
+                    rows.push(entry);
+                }
+                Ok(StatementRows(rows))
+            }
+        }
+
+        deserializer.deserialize_map(DataVisitor)
+    }
+}
+
+// The response of the statement API, of which only "data" is used
+struct StatementResponse(Option<StatementRows>);
+
+impl<'de> Deserialize<'de> for StatementResponse {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ResponseVisitor;
+
+        impl<'de> Visitor<'de> for ResponseVisitor {
+            type Value = StatementResponse;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "an object with the statement data")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<StatementResponse, A::Error> {
+                let mut data = None;
+                while let Some(key) = map.next_key::<String>()? {
+                    if key == "data" {
+                        data = Some(map.next_value::<StatementRows>()?);
+                    } else {
+                        map.next_value::<IgnoredAny>()?;
+                    }
+                }
+                Ok(StatementResponse(data))
+            }
+        }
+
+        deserializer.deserialize_map(ResponseVisitor)
+    }
+}
+
+// helper function to convert the statement data, an object of line item name to the values of
+// each period, into `Statement`
+fn parse_statement(body: &str) -> Result<Statement, Box<dyn std::error::Error>> {
+    let StatementResponse(rows) = serde_json::from_str(body)?;
+    let rows = rows.ok_or("Cannot find the statement data")?.0;
+
+    let text = |value: &Value| value.as_str().map_or_else(|| value.to_string(), String::from);
+
+    let mut statement = Statement::default();
+    for (name, values) in rows {
+        let values = values.as_array().ok_or(format!("Invalid values of line item {}", name))?;
+        if name == "Period End Date" || name == "Date" {
+            statement.periods = values.iter().map(text).collect();
+        } else {
+            statement.line_items.push(LineItem {
+                values: values.iter()
+                    .map(|value| value.as_f64().or_else(|| parse_number(&text(value))))
+                    .collect(),
+                name,
+            });
+        }
+    }
+
+    if statement.periods.is_empty() {
+        return Err("Cannot find the periods of the statement".into());
+    }
+    if let Some(item) = statement.line_items.iter().find(|item| item.values.len() != statement.periods.len()) {
+        return Err(format!(
+            "Expect {} values of line item {} in the statement, but got {}",
+            statement.periods.len(), item.name, item.values.len()
+        ).into());
+    }
+    Ok(statement)
+}
+
+/// A bar of the price history returned by `Tickers::history`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bar {
+    /// the start of the period of the bar
+    pub date: NaiveDateTime,
+    /// open price
+    pub open: f64,
+    /// high price
+    pub high: f64,
+    /// low price
+    pub low: f64,
+    /// close price
+    pub close: f64,
+    /// traded volume
+    pub volume: u64,
+}
+
+impl TableRow for Bar {
+
+    fn header() -> Vec<String> {
+        ["Date", "Open", "High", "Low", "Close", "Volume"].map(String::from).to_vec()
+    }
+
+    fn to_row(&self) -> Vec<String> {
+        vec![
+            self.date.to_string(),
+            self.open.to_string(),
+            self.high.to_string(),
+            self.low.to_string(),
+            self.close.to_string(),
+            self.volume.to_string(),
+        ]
+    }
+}
+
+// helper function to convert the columnar chart data (one array per field) into bars
+fn parse_history(data: &Value) -> Result<Vec<Bar>, Box<dyn std::error::Error>> {
+    let column = |key: &str| {
+        data.get(key)
+            .and_then(Value::as_array)
+            .ok_or(format!("Cannot find \"{}\" in the price history", key))
+    };
+    let (dates, opens, highs, lows, closes, volumes) =
+        (column("date")?, column("open")?, column("high")?, column("low")?, column("close")?, column("volume")?);
+
+    for (key, values) in [("open", opens), ("high", highs), ("low", lows), ("close", closes), ("volume", volumes)] {
+        if values.len() != dates.len() {
+            return Err(format!("Expect {} values of \"{}\" in the price history, but got {}", dates.len(), key, values.len()).into());
+        }
+    }
+
+    let number = |values: &Vec<Value>, i: usize, key: &str| {
+        values[i].as_f64().ok_or(format!("Invalid \"{}\" in the price history: {}", key, values[i]))
+    };
+
+    dates.iter().enumerate()
+        .map(|(i, date)| {
+            Ok(Bar {
+                date: parse_history_date(date).ok_or(format!("Invalid date in the price history: {}", date))?,
+                open: number(opens, i, "open")?,
+                high: number(highs, i, "high")?,
+                low: number(lows, i, "low")?,
+                close: number(closes, i, "close")?,
+                volume: number(volumes, i, "volume")? as u64,
+            })
+        })
+        .collect()
+}
+
+// helper function to parse a date of the price history, given either as unix timestamp or as `YYYYMMDD`
+fn parse_history_date(date: &Value) -> Option<NaiveDateTime> {
+    match date {
+        Value::Number(n) => DateTime::from_timestamp(n.as_i64()?, 0).map(|d| d.naive_utc()),
+        Value::String(s) => NaiveDate::parse_from_str(s, "%Y%m%d").ok()?.and_hms_opt(0, 0, 0),
+        _ => None,
     }
 }
 
@@ -169,3 +496,84 @@ impl Scrape<DictData> for Tickers
 
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_url() {
+        let tickers = Tickers::new("AAPL");
+        assert_eq!(tickers.get_history_url(TimeFrameType::Weekly), "https://finviz.com/api/quote.ashx?instrument=stock&ticker=AAPL&timeframe=w");
+    }
+
+    #[test]
+    fn test_ticker_chart_request() {
+        let request = Tickers::ticker_chart_request(TimeFrameType::Weekly, ChartType::LINE);
+        assert_eq!(request.to_file_name("AAPL"), "AAPL_weekly_line");
+        assert_eq!(request.to_url("AAPL"), "https://finviz.com/chart.ashx?t=AAPL&ty=l&ta=0&p=w");
+        let daily = Tickers::ticker_chart_request(TimeFrameType::Daily, ChartType::LINE);
+        assert_ne!(daily.to_file_name("AAPL"), request.to_file_name("AAPL"));
+    }
+
+    #[test]
+    fn test_statement_url() {
+        let tickers = Tickers::new("AAPL");
+        assert_eq!(tickers.get_statement_url(StatementType::BalanceSheet, Period::Quarterly), "https://finviz.com/api/statement.ashx?t=AAPL&s=BQ");
+    }
+
+    #[test]
+    fn test_parse_statement() {
+        let body = r#"{
+            "ticker": "AAPL",
+            "data": {
+                "Period End Date": ["9/30/2023", "9/24/2022"],
+                "Total Revenue": ["383,285.00", "394,328.00"],
+                "Net Income": ["96,995.00", "-"],
+                "EBITDA": [125820, 130541]
+            }
+        }"#;
+        let statement = parse_statement(body).unwrap();
+        assert_eq!(statement.periods, vec!["9/30/2023", "9/24/2022"]);
+        assert_eq!(statement.line_items.iter().map(|item| item.name.as_str()).collect::<Vec<_>>(), vec!["Total Revenue", "Net Income", "EBITDA"]);
+        assert_eq!(statement.get("Total Revenue").unwrap().values, vec![Some(383285.0), Some(394328.0)]);
+        assert_eq!(statement.get("Net Income").unwrap().values, vec![Some(96995.0), None]);
+        assert_eq!(statement.get("EBITDA").unwrap().values, vec![Some(125820.0), Some(130541.0)]);
+
+        let values = statement.values();
+        assert_eq!(values.len(), 6);
+        assert_eq!(values[3].to_row(), vec!["Net Income", "9/24/2022", "-"]);
+        assert!(parse_statement(r#"{"ticker": "AAPL"}"#).is_err());
+
+        // the line items must have a value per period
+        let body = r#"{"data": {"Period End Date": ["9/30/2023", "9/24/2022"], "Total Revenue": ["383,285.00"]}}"#;
+        assert_eq!(parse_statement(body).unwrap_err().to_string(), "Expect 2 values of line item Total Revenue in the statement, but got 1");
+    }
+
+    #[test]
+    fn test_parse_history() {
+        let data = serde_json::json!({
+            "ticker": "AAPL",
+            "date": [1684108800, "20230516"],
+            "open": [172.5, 171.99],
+            "high": [173.21, 173.14],
+            "low": [171.47, 171.8],
+            "close": [172.07, 172.07],
+            "volume": [37266659, 42110293],
+        });
+        let bars = parse_history(&data).unwrap();
+        assert_eq!(bars.len(), 2);
+        assert_eq!(bars[0].date.to_string(), "2023-05-15 00:00:00");
+        assert_eq!(bars[1].date.to_string(), "2023-05-16 00:00:00");
+        assert_eq!(bars[1].open, 171.99);
+        assert_eq!(bars[1].volume, 42110293);
+        assert!(parse_history(&serde_json::json!({"date": []})).is_err());
+
+        // the columns must have the same length
+        let mut short = data.clone();
+        short["close"] = serde_json::json!([172.07]);
+        assert_eq!(parse_history(&short).unwrap_err().to_string(), "Expect 2 values of \"close\" in the price history, but got 1");
+        let mut invalid = data;
+        invalid["volume"] = serde_json::json!([37266659, null]);
+        assert!(parse_history(&invalid).is_err());
+    }
+}
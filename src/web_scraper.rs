@@ -1,10 +1,12 @@
 use scraper::{Html, Selector};
 
 use reqwest::Client;
-use reqwest::header::USER_AGENT;
+use reqwest::header::{CONTENT_TYPE, USER_AGENT};
 use crate::common::TableData;
 
 use std::io::prelude::*;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 /// Sends an HTTP GET request to the specified URL using the provided client.
 ///
@@ -99,6 +101,141 @@ pub async fn get_html_body(url: &str) -> Result<String, Box<dyn std::error::Erro
     Ok(resp.text().await?)
 }
 
+/// An image downloaded by `get_chart_image`
+#[derive(Debug, Clone)]
+pub struct ChartImage {
+    /// raw bytes of the image
+    pub bytes: Vec<u8>,
+    /// content type detected from the bytes, e.g. `image/png`
+    pub content_type: String,
+}
+
+impl ChartImage {
+
+    /// Returns the file extension matching the content type, e.g. `png`
+    pub fn extension(&self) -> &str {
+        match self.content_type.as_str() {
+            "image/gif" => "gif",
+            "image/jpeg" => "jpg",
+            "image/webp" => "webp",
+            _ => "png",
+        }
+    }
+}
+
+// helper function to detect the image format by its magic bytes
+fn detect_image_type(bytes: &[u8]) -> Option<&'static str> {
+    match bytes {
+        [0x89, b'P', b'N', b'G', ..] => Some("image/png"),
+        [b'G', b'I', b'F', b'8', ..] => Some("image/gif"),
+        [0xFF, 0xD8, 0xFF, ..] => Some("image/jpeg"),
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => Some("image/webp"),
+        _ => None,
+    }
+}
+
+/// Retrieves the chart image from the specified chart URL into memory.
+///
+/// # Arguments
+///
+/// * `chart_url` - The URL of the chart image.
+///
+/// # Returns
+///
+/// Returns a `Result` containing the `ChartImage` if successful, or an error if the request fails or the
+/// response is not an image (e.g. an HTML error page).
+///
+pub async fn get_chart_image(chart_url: &str) -> Result<ChartImage, Box<dyn std::error::Error>> {
+    let client = Client::new();
+    let resp = send_request(&client, chart_url).await?;
+    let status = resp.status();
+    let header_type = resp.headers().get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("unknown")
+        .to_owned();
+    let bytes = resp.bytes().await?.to_vec();
+
+    match detect_image_type(&bytes) {
+        Some(content_type) if status.is_success() => Ok(ChartImage { bytes, content_type: content_type.to_owned() }),
+        _ => Err(format!("Response of {} is not an image (status: {}, content type: {})", chart_url, status, header_type).into()),
+    }
+}
+
+/// Writes the data to the file path atomically, by writing a temporary file next to it and renaming it.
+/// The temporary file name is unique to the process and the call, so concurrent writers of the same
+/// file do not collide, and the last rename wins.
+///
+/// # Arguments
+///
+/// * `file_path` - The path of the file to write.
+/// * `data` - The content of the file.
+///
+pub fn write_file_atomic(file_path: &str, data: &[u8]) -> std::io::Result<()> {
+    static TMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let path = Path::new(file_path);
+    let file_name = path.file_name()
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("Invalid file path: {}", file_path)))?;
+    let tmp_path = path.with_file_name(format!(
+        ".{}.{}.{}.tmp",
+        file_name.to_string_lossy(),
+        std::process::id(),
+        TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    let result = std::fs::File::create(&tmp_path)
+        .and_then(|mut file| {
+            file.write_all(data)?;
+            file.sync_all()
+        })
+        .and_then(|_| std::fs::rename(&tmp_path, path));
+
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp_path);
+    }
+    result
+}
+
+/// Saves the image to the output directory, which is created if missing, with the extension matching
+/// the image type.
+///
+/// # Arguments
+///
+/// * `image` - The downloaded image.
+/// * `out_dir` - The output directory, the current directory if empty.
+/// * `file_name` - The file name without extension.
+///
+/// # Returns
+///
+/// Returns a `Result` containing the file path of the saved image if successful, or an error if the file cannot be written.
+///
+pub fn save_image(image: &ChartImage, out_dir: &str, file_name: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let out_dir = if out_dir.is_empty() { "." } else { out_dir } ;
+    std::fs::create_dir_all(out_dir)?;
+
+    let file_path = format!("{}/{}.{}", out_dir, file_name, image.extension());
+    write_file_atomic(&file_path, &image.bytes)?;
+    Ok(file_path)
+}
+
+/// Retrieves the chart image from the specified chart URL and saves it to the file path.
+///
+/// # Arguments
+///
+/// * `chart_url` - The URL of the chart image.
+/// * `file_path` - The path of the saved image.
+///
+/// # Returns
+///
+/// Returns a `Result` containing the file path of the saved chart image if successful, or an error if the
+/// response is not an image or the file cannot be written.
+///
+pub async fn save_chart_image(chart_url: &str, file_path: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let image = get_chart_image(chart_url).await?;
+    write_file_atomic(file_path, &image.bytes)?;
+    Ok(file_path.to_owned())
+}
+
 /// Scrapes the chart image for a given ticker from the specified chart URL and saves it to the output directory.
 ///
 /// # Arguments
@@ -111,14 +248,49 @@ pub async fn get_html_body(url: &str) -> Result<String, Box<dyn std::error::Erro
 ///
 /// Returns a `Result` containing the file path of the saved chart image if successful, or an error message as a string if an error occurs.
 ///
+/// The file is named `{ticker}.png` whatever the chart is, so charts of different timeframes of a ticker
+/// overwrite each other.
+///
+#[deprecated(note = "use `Tickers::chart` with a `ChartRequest`, which names the file by the timeframe and chart type")]
 pub async fn scrape_chart_image(chart_url: &str, ticker: &str, out_dir: &str) -> Result<String, Box<dyn std::error::Error>> {
-    println!("Getting image for ticker {} from URL: {} (out dir={})", ticker, chart_url, out_dir);
     let file_path = format!("{}/{}.png", out_dir, ticker);
-    let mut file = std::fs::File::create(&file_path).unwrap();
+    save_chart_image(chart_url, &file_path).await
+}
 
-    let client = Client::new();
-    let resp = send_request(&client, chart_url).await?;
-    let bytes_data = resp.bytes().await?;
-    file.write_all(bytes_data.as_ref())?;
-    Ok(file_path)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_image_type() {
+        assert_eq!(detect_image_type(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A]), Some("image/png"));
+        assert_eq!(detect_image_type(b"GIF89a"), Some("image/gif"));
+        assert_eq!(detect_image_type(&[0xFF, 0xD8, 0xFF, 0xE0]), Some("image/jpeg"));
+        assert_eq!(detect_image_type(b"<!DOCTYPE html>"), None);
+    }
+
+    #[test]
+    fn test_write_file_atomic() {
+        let dir = std::env::temp_dir().join(format!("finviz_rs_write_file_atomic_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file_path = dir.join("chart.png");
+        let file_path = file_path.to_str().unwrap();
+
+        assert!(write_file_atomic(file_path, b"data").is_ok());
+        assert_eq!(std::fs::read(file_path).unwrap(), b"data");
+
+        // concurrent writers of the same file do not share the temporary file
+        let writers = (0..8)
+            .map(|i| {
+                let file_path = file_path.to_string();
+                std::thread::spawn(move || write_file_atomic(&file_path, format!("data {}", i).as_bytes()))
+            })
+            .collect::<Vec<_>>();
+        for writer in writers {
+            assert!(writer.join().unwrap().is_ok());
+        }
+        assert!(std::fs::read_to_string(file_path).unwrap().starts_with("data "));
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }